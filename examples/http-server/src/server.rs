@@ -22,7 +22,7 @@ use phper::{
     values::ZVal,
 };
 use std::{cell::RefCell, collections::HashMap, error::Error, net::SocketAddr};
-use tokio::{net::TcpListener, runtime};
+use tokio::net::TcpListener;
 
 const HTTP_SERVER_CLASS_NAME: &str = "HttpServer\\HttpServer";
 
@@ -170,14 +170,10 @@ pub fn make_server_class(
             Ok::<_, Box<dyn Error>>(())
         };
 
-        // Build the tokio runtime, with the current thread scheduler, block on
-        // listening socket addr and handling the server request.
-        runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap()
-            .block_on(fut)
-            .map_err(HttpServerError::new)?;
+        // Block on listening socket addr and handling the server request, by the
+        // runtime managed by phper, which is the current thread runtime of this
+        // thread by default.
+        phper::runtime::block_on(fut)?.map_err(HttpServerError::new)?;
 
         Ok::<_, phper::Error>(())
     });
@@ -0,0 +1,75 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{modules::Module, php_zend_extension, zend_extensions::ZendExtension};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+static ACTIVATED: AtomicI64 = AtomicI64::new(0);
+static STATEMENTS: AtomicI64 = AtomicI64::new(0);
+static CALLS: AtomicI64 = AtomicI64::new(0);
+
+/// The companion module, started up by the zend extension, exposes the
+/// counters to the scripts.
+fn get_module() -> Module {
+    let mut module = Module::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_PKG_AUTHORS"),
+    );
+
+    module.add_function("tracer_started", |_| {
+        phper::ok(STARTED.load(Ordering::Relaxed))
+    });
+    module.add_function("tracer_activated", |_| {
+        phper::ok(ACTIVATED.load(Ordering::Relaxed))
+    });
+    module.add_function("tracer_statements", |_| {
+        phper::ok(STATEMENTS.load(Ordering::Relaxed))
+    });
+    module.add_function("tracer_calls", |_| phper::ok(CALLS.load(Ordering::Relaxed)));
+
+    module
+}
+
+/// This is the entry of zend extension, the attribute macro
+/// `php_zend_extension` will generate the `zend_extension_entry` symbols.
+#[php_zend_extension]
+pub fn get_zend_extension() -> ZendExtension {
+    let mut extension = ZendExtension::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_PKG_AUTHORS"),
+    );
+
+    extension.set_module(get_module());
+
+    extension.on_startup(|| {
+        STARTED.store(true, Ordering::Relaxed);
+    });
+
+    // The counters are reset at the beginning of request, except the
+    // activated times.
+    extension.on_activate(|| {
+        ACTIVATED.fetch_add(1, Ordering::Relaxed);
+        STATEMENTS.store(0, Ordering::Relaxed);
+        CALLS.store(0, Ordering::Relaxed);
+    });
+
+    extension.on_statement(|_| {
+        STATEMENTS.fetch_add(1, Ordering::Relaxed);
+    });
+
+    extension.on_fcall_begin(|_| {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+    });
+
+    extension
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper_test::{cargo::CargoBuilder, cli::test_php_script_with_ini, log};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    str,
+    sync::LazyLock,
+};
+
+pub static DYLIB_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    log::setup();
+    let result = CargoBuilder::new()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .build()
+        .unwrap();
+    result.get_cdylib().unwrap()
+});
+
+pub static TESTS_PHP_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("php")
+});
+
+#[test]
+fn test_php() {
+    // The build id is left empty in `extension_version_info`, so the zend
+    // extension is only loaded when `build_id_check` passes.
+    test_php_script_with_ini(
+        &[&format!("zend_extension={}", DYLIB_PATH.display())],
+        TESTS_PHP_DIR.join("test.php"),
+        |output| {
+            let stdout = str::from_utf8(&output.stdout).unwrap();
+            let stderr = str::from_utf8(&output.stderr).unwrap();
+            output.status.success()
+                && !stdout.contains("Cannot load")
+                && !stderr.contains("Cannot load")
+        },
+    );
+}
+
+#[test]
+fn test_php_load_by_extension() {
+    test_php_script_with_ini(
+        &[
+            "display_startup_errors=On",
+            &format!("extension={}", DYLIB_PATH.display()),
+        ],
+        TESTS_PHP_DIR.join("load_by_extension.php"),
+        |output| {
+            let stdout = str::from_utf8(&output.stdout).unwrap();
+            let stderr = str::from_utf8(&output.stderr).unwrap();
+            (stdout.contains("appears to be a Zend Extension")
+                || stderr.contains("appears to be a Zend Extension"))
+                && stdout.contains("bool(false)")
+        },
+    );
+}
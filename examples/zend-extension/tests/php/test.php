@@ -0,0 +1,45 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+ini_set("display_errors", "On");
+ini_set("display_startup_errors", "On");
+error_reporting(E_ALL);
+
+// The zend extension and the companion module are both loaded.
+assert_true(in_array("tracer", get_loaded_extensions(true), true));
+assert_true(extension_loaded("tracer"));
+
+// The startup hook is called once, and the activate hook is called at the
+// beginning of the request.
+assert_eq(tracer_started(), true);
+assert_eq(tracer_activated(), 1);
+
+// The statement and function call hooks are called since the script is
+// compiled with the extended info.
+function tracer_test_noop() {}
+
+$calls = tracer_calls();
+tracer_test_noop();
+tracer_test_noop();
+assert_true(tracer_calls() >= $calls + 2);
+assert_true(tracer_statements() > 0);
+
+function assert_true($value) {
+    assert_eq($value, true);
+}
+
+function assert_eq($left, $right) {
+    if ($left !== $right) {
+        throw new AssertionError(sprintf("left != right,\n left: %s,\n right: %s", var_export($left, true), var_export($right, true)));
+    }
+}
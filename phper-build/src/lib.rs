@@ -37,6 +37,13 @@ pub fn register_configures() {
         PHP_RELEASE_VERSION
     );
 
+    if (PHP_MAJOR_VERSION, PHP_MINOR_VERSION) >= (8, 4) {
+        println!("cargo::rustc-cfg=phper_php_8_4_or_later");
+    }
+    if (PHP_MAJOR_VERSION, PHP_MINOR_VERSION) >= (8, 5) {
+        println!("cargo::rustc-cfg=phper_php_8_5_or_later");
+    }
+
     if PHP_DEBUG != 0 {
         println!("cargo::rustc-cfg=phper_debug");
     }
@@ -75,6 +75,9 @@ pub mod _06_module {
     #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
     #[doc = include_str!("../doc/_06_module/_08_register_enum/index.md")]
     pub mod _08_register_enum {}
+
+    #[doc = include_str!("../doc/_06_module/_09_register_trait/index.md")]
+    pub mod _09_register_trait {}
 }
 
 /// TODO
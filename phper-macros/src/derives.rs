@@ -8,4 +8,189 @@
 // NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
 // See the Mulan PSL v2 for more details.
 
-// TODO Implement derive macros (e.g. `#[derive(ZValConvert)]`).
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Lit, LitStr, parse_macro_input};
+
+pub(crate) fn derive_php_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    php_enum(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The backing value of the variant.
+enum CaseValue {
+    Pure,
+    Int(TokenStream2),
+    String(LitStr),
+}
+
+/// The options of `#[php_enum(...)]`.
+#[derive(Default)]
+struct PhpEnumOptions {
+    name: Option<LitStr>,
+    value: Option<Lit>,
+}
+
+fn parse_options(attrs: &[Attribute]) -> syn::Result<PhpEnumOptions> {
+    let mut options = PhpEnumOptions::default();
+    for attr in attrs {
+        if !attr.path().is_ident("php_enum") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("value") {
+                options.value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `php_enum` option"))
+            }
+        })?;
+    }
+    Ok(options)
+}
+
+fn php_enum(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "`PhpEnum` can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`PhpEnum` can't be derived for generic enums",
+        ));
+    }
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            "`PhpEnum` can't be derived for empty enums",
+        ));
+    }
+
+    let options = parse_options(&input.attrs)?;
+    if let Some(value) = options.value {
+        return Err(Error::new_spanned(
+            value,
+            "`value` is only supported for variants",
+        ));
+    }
+    let enum_name = options
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(|| ident.to_string());
+
+    let mut variants = Vec::new();
+    let mut case_names = Vec::new();
+    let mut case_values = Vec::new();
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "`PhpEnum` can only be derived for fieldless enums",
+            ));
+        }
+
+        let options = parse_options(&variant.attrs)?;
+        let case_value = match (options.value, &variant.discriminant) {
+            (Some(Lit::Str(value)), None) => CaseValue::String(value),
+            (Some(Lit::Int(value)), None) => CaseValue::Int(quote! { #value }),
+            (Some(value), None) => {
+                return Err(Error::new_spanned(
+                    value,
+                    "the backing value should be int or string",
+                ));
+            }
+            (Some(value), Some(_)) => {
+                return Err(Error::new_spanned(
+                    value,
+                    "the backing value is already specified by the discriminant",
+                ));
+            }
+            (None, Some((_, discriminant))) => CaseValue::Int(quote! { #discriminant }),
+            (None, None) => CaseValue::Pure,
+        };
+
+        variants.push(&variant.ident);
+        case_names.push(
+            options
+                .name
+                .map(|name| name.value())
+                .unwrap_or_else(|| variant.ident.to_string()),
+        );
+        case_values.push(case_value);
+    }
+
+    let backing = match case_values.first() {
+        Some(CaseValue::Int(_)) => quote! { i64 },
+        Some(CaseValue::String(_)) => quote! { ::std::string::String },
+        _ => quote! { () },
+    };
+
+    let mut value_arms = Vec::new();
+    for (variant, case_value) in variants.iter().zip(&case_values) {
+        let value = match (case_values.first(), case_value) {
+            (Some(CaseValue::Pure), CaseValue::Pure) => quote! { () },
+            (Some(CaseValue::Int(_)), CaseValue::Int(value)) => quote! { #value },
+            (Some(CaseValue::String(_)), CaseValue::String(value)) => {
+                quote! { ::std::string::ToString::to_string(#value) }
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "all the variants should have the backing values of the same type, or none of \
+                     them",
+                ));
+            }
+        };
+        value_arms.push(quote! { Self::#variant => #value });
+    }
+
+    Ok(quote! {
+        impl ::phper::enums::PhpEnum for #ident {
+            type Backing = #backing;
+
+            const ENUM_NAME: &'static str = #enum_name;
+
+            const VARIANTS: &'static [Self] = &[#(Self::#variants),*];
+
+            fn case_name(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #case_names,)*
+                }
+            }
+
+            fn case_value(&self) -> Self::Backing {
+                match self {
+                    #(#value_arms,)*
+                }
+            }
+
+            fn from_case_name(name: &str) -> ::std::option::Option<Self> {
+                match name {
+                    #(#case_names => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<&::phper::values::ZVal> for #ident {
+            type Error = ::phper::Error;
+
+            fn try_from(value: &::phper::values::ZVal) -> ::std::result::Result<Self, Self::Error> {
+                <Self as ::phper::enums::PhpEnum>::from_zval(value)
+            }
+        }
+    })
+}
@@ -45,3 +45,52 @@ pub(crate) fn php_get_module(_attr: TokenStream, input: TokenStream) -> TokenStr
 
     result.into()
 }
+
+pub(crate) fn php_zend_extension(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemFn);
+
+    let name = &input.sig.ident;
+
+    if !input.sig.inputs.is_empty() {
+        return quote! { compile_error!("function with attribute `php_zend_extension` mustn't have arguments"); }.into();
+    }
+
+    let result = quote! {
+        #input
+
+        #[unsafe(no_mangle)]
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        pub static extension_version_info: ::phper::zend_extensions::ZendExtensionVersionInfo =
+            ::phper::zend_extensions::ZendExtensionVersionInfo::new();
+
+        #[unsafe(no_mangle)]
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        pub static zend_extension_entry: ::phper::zend_extensions::ZendExtensionEntry = {
+            unsafe extern "C" fn startup(
+                extension: *mut ::phper::sys::zend_extension,
+            ) -> ::std::os::raw::c_int {
+                let get_zend_extension: fn() -> ::phper::zend_extensions::ZendExtension = #name;
+                unsafe {
+                    ::phper::zend_extensions::ZendExtensionEntry::startup(extension, get_zend_extension)
+                }
+            }
+
+            const NAME: &::std::ffi::CStr =
+                match ::std::ffi::CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_NAME"), "\0").as_bytes()) {
+                    Ok(name) => name,
+                    Err(_) => panic!("package name contains '\\0'"),
+                };
+            const VERSION: &::std::ffi::CStr =
+                match ::std::ffi::CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+                    Ok(version) => version,
+                    Err(_) => panic!("package version contains '\\0'"),
+                };
+
+            ::phper::zend_extensions::ZendExtensionEntry::new(NAME, VERSION, startup)
+        };
+    };
+
+    result.into()
+}
@@ -49,3 +49,64 @@ use proc_macro::TokenStream;
 pub fn php_get_module(attr: TokenStream, input: TokenStream) -> TokenStream {
     inner::php_get_module(attr, input)
 }
+
+/// PHP zend extension entry, emits the `extension_version_info` and
+/// `zend_extension_entry` symbols for loading by `zend_extension=`.
+///
+/// The function is called when the zend extension starts up.
+///
+/// # Examples
+///
+/// ```no_test
+/// use phper::{php_zend_extension, zend_extensions::ZendExtension};
+///
+/// #[php_zend_extension]
+/// pub fn get_zend_extension() -> ZendExtension {
+///     let mut extension = ZendExtension::new(
+///         env!("CARGO_CRATE_NAME"),
+///         env!("CARGO_PKG_VERSION"),
+///         env!("CARGO_PKG_AUTHORS"),
+///     );
+///
+///     // ...
+///
+///     extension
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn php_zend_extension(attr: TokenStream, input: TokenStream) -> TokenStream {
+    inner::php_zend_extension(attr, input)
+}
+
+/// Derive `phper::enums::PhpEnum` for the fieldless enum, and the conversion
+/// from `phper::values::ZVal`.
+///
+/// The backing type is decided by the variants: pure enum if no variant has
+/// the backing value, int backed enum if the variants have the discriminants
+/// or `#[php_enum(value = 1)]`, string backed enum if the variants have
+/// `#[php_enum(value = "a")]`.
+///
+/// The PHP enum name and case names can be renamed by
+/// `#[php_enum(name = "...")]`.
+///
+/// # Examples
+///
+/// ```no_test
+/// use phper::{PhpEnum, enums::PhpEnum as _, modules::Module};
+///
+/// #[derive(PhpEnum)]
+/// #[php_enum(name = "App\\Level")]
+/// enum Level {
+///     #[php_enum(name = "LOW")]
+///     Low = 1,
+///     #[php_enum(name = "HIGH")]
+///     High = 10,
+/// }
+///
+/// let mut module = Module::new("app", "0.1.0", "");
+/// module.add_enum(Level::enum_entity());
+/// ```
+#[proc_macro_derive(PhpEnum, attributes(php_enum))]
+pub fn derive_php_enum(input: TokenStream) -> TokenStream {
+    derives::derive_php_enum(input)
+}
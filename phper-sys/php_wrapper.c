@@ -16,14 +16,28 @@
 #include <ext/standard/info.h>
 #include <main/SAPI.h>
 #include <zend_exceptions.h>
+#include <zend_extensions.h>
 #include <zend_interfaces.h>
 
 #if PHP_MAJOR_VERSION >= 8
 #include <zend_observer.h>
 #endif
 
+#if PHP_VERSION_ID >= 70400
+#include <zend_weakrefs.h>
+#endif
+
 #if PHP_VERSION_ID >= 80100
 #include <zend_enum.h>
+#include <zend_fibers.h>
+#endif
+
+#if PHP_MAJOR_VERSION >= 8
+#include <zend_attributes.h>
+#endif
+
+#if PHP_VERSION_ID >= 80400
+#include <zend_lazy_objects.h>
 #endif
 
 typedef ZEND_INI_MH(phper_zend_ini_mh);
@@ -118,6 +132,10 @@ zend_function *phper_z_func_p(const zval *zv) {
     return Z_FUNC_P(zv);
 }
 
+zval *phper_z_indirect_p(const zval *zv) {
+    return Z_INDIRECT_P(zv);
+}
+
 void *phper_z_ptr_p(const zval *zv) {
     return Z_PTR_P(zv);
 }
@@ -294,6 +312,62 @@ void phper_array_init(zval *arg) {
     array_init(arg);
 }
 
+static zend_string *phper_persistent_interned_string(const char *s,
+                                                     size_t len) {
+#if PHP_VERSION_ID >= 70300
+    return zend_string_init_interned(s, len, 1);
+#else
+    return zend_new_interned_string(zend_string_init(s, len, 1));
+#endif
+}
+
+void phper_zval_interned_stringl(zval *zv, const char *s, size_t len) {
+    ZVAL_INTERNED_STR(zv, phper_persistent_interned_string(s, len));
+}
+
+HashTable *phper_zend_new_persistent_array(uint32_t size) {
+    HashTable *ht = pemalloc(sizeof(HashTable), 1);
+    zend_hash_init(ht, size, NULL, NULL, 1);
+    return ht;
+}
+
+void phper_zend_persistent_array_str_update(HashTable *ht, const char *key,
+                                            size_t len, zval *pData) {
+    zend_string *k = phper_persistent_interned_string(key, len);
+    zend_symtable_update(ht, k, pData);
+}
+
+// Make the persistent array immutable like the opcache does, so it won't be
+// refcounted and will be separated before modified.
+void phper_zval_immutable_arr(zval *zv, HashTable *ht) {
+    ZVAL_ARR(zv, ht);
+#if PHP_VERSION_ID >= 70300
+    GC_SET_REFCOUNT(ht, 2);
+    GC_ADD_FLAGS(ht, GC_IMMUTABLE);
+    Z_TYPE_FLAGS_P(zv) = 0;
+#elif PHP_VERSION_ID >= 70200
+    GC_REFCOUNT(ht) = 2;
+    GC_FLAGS(ht) |= IS_ARRAY_IMMUTABLE;
+    Z_TYPE_FLAGS_P(zv) = IS_TYPE_COPYABLE;
+#else
+    GC_REFCOUNT(ht) = 2;
+    GC_FLAGS(ht) |= IS_ARRAY_IMMUTABLE;
+    Z_TYPE_FLAGS_P(zv) = IS_TYPE_IMMUTABLE;
+#endif
+}
+
+void phper_zend_persistent_array_free(HashTable *ht) {
+    zval *val;
+    ZEND_HASH_FOREACH_VAL(ht, val) {
+        if (Z_TYPE_P(val) == IS_ARRAY) {
+            phper_zend_persistent_array_free(Z_ARR_P(val));
+        }
+    }
+    ZEND_HASH_FOREACH_END();
+    zend_hash_destroy(ht);
+    pefree(ht, 1);
+}
+
 void *phper_zend_hash_str_find_ptr(const HashTable *ht, const char *str,
                                    size_t len) {
     return zend_hash_str_find_ptr(ht, str, len);
@@ -391,6 +465,213 @@ uint32_t phper_zend_object_gc_refcount(const zend_object *obj) {
     return GC_REFCOUNT(obj);
 }
 
+#if PHP_VERSION_ID >= 70100
+#define PHPER_FAKE_SCOPE EG(fake_scope)
+#else
+#define PHPER_FAKE_SCOPE EG(scope)
+#endif
+
+static zend_property_info *phper_find_property_info(zend_class_entry *ce,
+                                                    zend_string *key) {
+    zend_property_info *property_info;
+    zend_class_entry *old_scope = PHPER_FAKE_SCOPE;
+
+    if (ZSTR_LEN(key) > 0 && ZSTR_VAL(key)[0] == '\0') {
+        const char *class_name, *prop_name;
+        size_t prop_name_len;
+        if (zend_unmangle_property_name_ex(key, &class_name, &prop_name,
+                                           &prop_name_len) != SUCCESS) {
+            return NULL;
+        }
+        if (class_name[0] != '*') {
+            zend_string *cname =
+                zend_string_init(class_name, strlen(class_name), 0);
+            PHPER_FAKE_SCOPE = zend_lookup_class(cname);
+            zend_string_release(cname);
+        } else {
+            PHPER_FAKE_SCOPE = ce;
+        }
+        zend_string *name = zend_string_init(prop_name, prop_name_len, 0);
+        property_info = zend_get_property_info(ce, name, 1);
+        zend_string_release(name);
+    } else {
+        PHPER_FAKE_SCOPE = ce;
+        property_info = zend_get_property_info(ce, key, 1);
+    }
+
+    PHPER_FAKE_SCOPE = old_scope;
+
+    if (property_info == ZEND_WRONG_PROPERTY_INFO ||
+        (property_info && (property_info->flags & ZEND_ACC_STATIC))) {
+        return NULL;
+    }
+    return property_info;
+}
+
+bool phper_object_init_with_properties(zval *arg, zend_class_entry *ce,
+                                       HashTable *properties) {
+    HashTable *normalized;
+    zend_string *key;
+    zend_ulong h;
+    zval *value;
+
+    if (object_init_ex(arg, ce) != SUCCESS) {
+        return false;
+    }
+
+#if PHP_VERSION_ID >= 70300
+    normalized = zend_new_array(zend_hash_num_elements(properties));
+#else
+    ALLOC_HASHTABLE(normalized);
+    zend_hash_init(normalized, zend_hash_num_elements(properties), NULL,
+                   ZVAL_PTR_DTOR, 0);
+#endif
+
+    // Use the mangled names of the declared properties, so the private and
+    // protected properties can be found by `object_properties_load`, and
+    // verify the values of the typed properties.
+    ZEND_HASH_FOREACH_KEY_VAL(properties, h, key, value) {
+        zval tmp;
+        ZVAL_DEREF(value);
+        ZVAL_COPY(&tmp, value);
+
+        if (key == NULL) {
+            zend_hash_index_update(normalized, h, &tmp);
+            continue;
+        }
+
+        zend_property_info *property_info = phper_find_property_info(ce, key);
+        if (property_info == NULL) {
+            zend_hash_update(normalized, key, &tmp);
+            continue;
+        }
+
+#if PHP_VERSION_ID >= 70400
+        if (ZEND_TYPE_IS_SET(property_info->type) &&
+            !zend_verify_property_type(property_info, &tmp, 1)) {
+            zval_ptr_dtor(&tmp);
+            zend_array_destroy(normalized);
+            // Skip the destructor since the object isn't constructed.
+            zend_object_store_ctor_failed(Z_OBJ_P(arg));
+            zval_ptr_dtor(arg);
+            ZVAL_UNDEF(arg);
+            return false;
+        }
+#endif
+
+        zend_hash_update(normalized, property_info->name, &tmp);
+    }
+    ZEND_HASH_FOREACH_END();
+
+    object_properties_load(Z_OBJ_P(arg), normalized);
+    zend_array_destroy(normalized);
+
+    if (EG(exception)) {
+        zend_object_store_ctor_failed(Z_OBJ_P(arg));
+        zval_ptr_dtor(arg);
+        ZVAL_UNDEF(arg);
+        return false;
+    }
+    return true;
+}
+
+HashTable *phper_zend_object_get_properties_for(zend_object *obj) {
+    zval object;
+    ZVAL_OBJ(&object, obj);
+#if PHP_VERSION_ID >= 70400
+    return zend_get_properties_for(&object, ZEND_PROP_PURPOSE_ARRAY_CAST);
+#else
+    // The properties table is borrowed before PHP 7.4, so not to be released.
+    return Z_OBJ_HT(object)->get_properties(&object);
+#endif
+}
+
+void phper_zend_object_release_properties(HashTable *ht) {
+#if PHP_VERSION_ID >= 70400
+    zend_release_properties(ht);
+#endif
+}
+
+bool phper_zend_object_has_property(zend_object *obj, zend_class_entry *scope,
+                                    const char *name, size_t name_len,
+                                    bool check_empty) {
+    // 0 is `ZEND_PROPERTY_ISSET`, 1 is `ZEND_PROPERTY_NOT_EMPTY`.
+    int has_set_exists = check_empty ? 1 : 0;
+    int result;
+    zend_class_entry *old_scope = PHPER_FAKE_SCOPE;
+    PHPER_FAKE_SCOPE = scope;
+#if PHP_VERSION_ID >= 80000
+    zend_string *member = zend_string_init(name, name_len, 0);
+    result = obj->handlers->has_property(obj, member, has_set_exists, NULL);
+    zend_string_release(member);
+#else
+    zval object, member;
+    ZVAL_OBJ(&object, obj);
+    ZVAL_STRINGL(&member, name, name_len);
+    result =
+        obj->handlers->has_property(&object, &member, has_set_exists, NULL);
+    zval_ptr_dtor(&member);
+#endif
+    PHPER_FAKE_SCOPE = old_scope;
+    return result != 0;
+}
+
+void phper_zend_object_unset_property(zend_object *obj, zend_class_entry *scope,
+                                      const char *name, size_t name_len) {
+    zend_class_entry *old_scope = PHPER_FAKE_SCOPE;
+    PHPER_FAKE_SCOPE = scope;
+#if PHP_VERSION_ID >= 80000
+    zend_string *member = zend_string_init(name, name_len, 0);
+    obj->handlers->unset_property(obj, member, NULL);
+    zend_string_release(member);
+#else
+    zval object, member;
+    ZVAL_OBJ(&object, obj);
+    ZVAL_STRINGL(&member, name, name_len);
+    obj->handlers->unset_property(&object, &member, NULL);
+    zval_ptr_dtor(&member);
+#endif
+    PHPER_FAKE_SCOPE = old_scope;
+}
+
+#if PHP_VERSION_ID >= 70400
+void phper_zend_weakref_create(zend_object *obj, zval *return_value) {
+    zval object;
+    ZVAL_OBJ(&object, obj);
+    zend_call_method_with_1_params(NULL, zend_ce_weakref, NULL, "create",
+                                   return_value, &object);
+}
+
+void phper_zend_weakref_get(zend_object *weakref, zval *return_value) {
+#if PHP_VERSION_ID >= 80000
+    zend_call_method_with_0_params(weakref, zend_ce_weakref, NULL, "get",
+                                   return_value);
+#else
+    zval object;
+    ZVAL_OBJ(&object, weakref);
+    zend_call_method_with_0_params(&object, zend_ce_weakref, NULL, "get",
+                                   return_value);
+#endif
+}
+#endif
+
+#if PHP_VERSION_ID >= 80100
+void phper_zend_weakrefs_hash_init(HashTable *ht, dtor_func_t destructor) {
+    zend_hash_init(ht, 8, NULL, destructor, 0);
+}
+
+bool phper_zend_weakrefs_hash_add_ptr(HashTable *ht, zend_object *key,
+                                      void *ptr) {
+    zval value;
+    ZVAL_PTR(&value, ptr);
+    return zend_weakrefs_hash_add(ht, key, &value) != NULL;
+}
+
+bool phper_zend_weakrefs_hash_del(HashTable *ht, zend_object *key) {
+    return zend_weakrefs_hash_del(ht, key) == SUCCESS;
+}
+#endif
+
 // ==================================================
 // class apis:
 // ==================================================
@@ -414,6 +695,408 @@ zend_class_entry *phper_get_parent_class(zend_class_entry *ce) {
     return ce->parent;
 }
 
+#if PHP_VERSION_ID >= 70400
+void phper_zend_declare_typed_property(zend_class_entry *ce, const char *name,
+                                       size_t name_len, zval *default_value,
+                                       int access_type, uint32_t type_code,
+                                       const char *class_name,
+                                       bool allow_null) {
+    zend_string *name_str = zend_string_init_interned(name, name_len, 1);
+    zend_type type;
+#if PHP_VERSION_ID >= 80000
+    if (class_name != NULL) {
+        zend_string *class_name_str =
+            zend_string_init_interned(class_name, strlen(class_name), 1);
+        type = (zend_type)ZEND_TYPE_INIT_CLASS(class_name_str, allow_null, 0);
+    } else {
+        type = (zend_type)ZEND_TYPE_INIT_CODE(type_code, allow_null, 0);
+    }
+#else
+    if (class_name != NULL) {
+        zend_string *class_name_str =
+            zend_string_init_interned(class_name, strlen(class_name), 1);
+        type = ZEND_TYPE_ENCODE_CLASS(class_name_str, allow_null);
+    } else {
+        type = ZEND_TYPE_ENCODE(type_code, allow_null);
+    }
+#endif
+    zend_declare_typed_property(ce, name_str, default_value, access_type, NULL,
+                                type);
+    zend_string_release(name_str);
+}
+#endif
+
+#if PHP_VERSION_ID < 80000
+static void phper_zend_add_magic_method(zend_class_entry *ce, zend_function *fn,
+                                        zend_string *lcname) {
+    if (zend_string_equals_literal(lcname, ZEND_CONSTRUCTOR_FUNC_NAME)) {
+        ce->constructor = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_DESTRUCTOR_FUNC_NAME)) {
+        ce->destructor = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_CLONE_FUNC_NAME)) {
+        ce->clone = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_GET_FUNC_NAME)) {
+        ce->__get = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_SET_FUNC_NAME)) {
+        ce->__set = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_UNSET_FUNC_NAME)) {
+        ce->__unset = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_ISSET_FUNC_NAME)) {
+        ce->__isset = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_CALL_FUNC_NAME)) {
+        ce->__call = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_CALLSTATIC_FUNC_NAME)) {
+        ce->__callstatic = fn;
+    } else if (zend_string_equals_literal(lcname, ZEND_TOSTRING_FUNC_NAME)) {
+        ce->__tostring = fn;
+    }
+}
+#endif
+
+// Register the methods into a standalone function table, so the magic methods
+// are bound to the class without being visible to userland.
+//
+// The magic methods are dispatched by the standard object handlers, rather
+// than overriding `read_property` and the others, so the recursion guards, the
+// visibility checks, and the inheritance by subclasses (including the userland
+// ones, which get the standard handlers) are the same as the magic methods
+// declared in PHP, without wrapping the handler signatures which differ across
+// the PHP versions.
+HashTable *
+phper_zend_register_hidden_methods(zend_class_entry *ce,
+                                   const zend_function_entry *functions) {
+    HashTable *function_table = pemalloc(sizeof(HashTable), 1);
+    zend_hash_init(function_table, 8, NULL, ZEND_FUNCTION_DTOR, 1);
+
+#if PHP_VERSION_ID < 80000
+    // The magic method slots are all reset by `zend_register_functions`.
+    zend_class_entry saved = *ce;
+    zend_register_functions(ce, functions, function_table, MODULE_PERSISTENT);
+#define PHPER_RESTORE_MAGIC(field)                                             \
+    if (ce->field == NULL) {                                                   \
+        ce->field = saved.field;                                               \
+    }
+    PHPER_RESTORE_MAGIC(constructor)
+    PHPER_RESTORE_MAGIC(destructor)
+    PHPER_RESTORE_MAGIC(clone)
+    PHPER_RESTORE_MAGIC(__get)
+    PHPER_RESTORE_MAGIC(__set)
+    PHPER_RESTORE_MAGIC(__unset)
+    PHPER_RESTORE_MAGIC(__isset)
+    PHPER_RESTORE_MAGIC(__call)
+    PHPER_RESTORE_MAGIC(__callstatic)
+    PHPER_RESTORE_MAGIC(__tostring)
+    PHPER_RESTORE_MAGIC(__debugInfo)
+    PHPER_RESTORE_MAGIC(serialize_func)
+    PHPER_RESTORE_MAGIC(unserialize_func)
+#undef PHPER_RESTORE_MAGIC
+#else
+    zend_register_functions(ce, functions, function_table, MODULE_PERSISTENT);
+#endif
+
+    if (ce->__get || ce->__set || ce->__isset || ce->__unset) {
+        ce->ce_flags |= ZEND_ACC_USE_GUARDS;
+    }
+
+    return function_table;
+}
+
+// Free the table returned by `phper_zend_register_hidden_methods`, the class
+// only keeps the pointers of the magic methods, which aren't dereferenced when
+// the class is destroyed after `MSHUTDOWN`.
+void phper_zend_destroy_hidden_methods(HashTable *function_table) {
+#if PHP_VERSION_ID >= 80000
+    zend_function *fn;
+    ZEND_HASH_FOREACH_PTR(function_table, fn) {
+        if (fn->common.fn_flags &
+            (ZEND_ACC_HAS_RETURN_TYPE | ZEND_ACC_HAS_TYPE_HINTS)) {
+            zend_free_internal_arg_info(&fn->internal_function);
+        }
+    }
+    ZEND_HASH_FOREACH_END();
+#endif
+    zend_hash_destroy(function_table);
+    pefree(function_table, 1);
+}
+
+// Copy the internal methods and properties of a trait into an internal class,
+// the members declared by the class itself take precedence, and the methods
+// of the trait override the inherited ones.
+void phper_zend_class_use_trait(zend_class_entry *ce,
+                                zend_class_entry *trait) {
+    zend_string *key;
+    zend_function *fn;
+    zend_property_info *prop;
+
+    ZEND_HASH_FOREACH_STR_KEY_PTR(&trait->function_table, key, fn) {
+        zend_function *existing_fn, *new_fn;
+
+        if (fn->type != ZEND_INTERNAL_FUNCTION) {
+            continue;
+        }
+        existing_fn = zend_hash_find_ptr(&ce->function_table, key);
+        if (existing_fn != NULL && existing_fn->common.scope == ce) {
+            continue;
+        }
+
+        new_fn = pemalloc(sizeof(zend_internal_function), 1);
+        memcpy(new_fn, fn, sizeof(zend_internal_function));
+        new_fn->common.scope = ce;
+        zend_string_addref(new_fn->common.function_name);
+#if PHP_VERSION_ID >= 80000
+        if (new_fn->common.attributes) {
+            GC_ADDREF(new_fn->common.attributes);
+        }
+#endif
+
+#if PHP_VERSION_ID >= 80000
+        // The arg info of class methods is freed with the class, so the copy
+        // needs its own one.
+        if ((fn->common.fn_flags &
+             (ZEND_ACC_HAS_RETURN_TYPE | ZEND_ACC_HAS_TYPE_HINTS)) &&
+            fn->internal_function.arg_info) {
+            uint32_t num_args = fn->common.num_args + 1;
+            zend_internal_arg_info *arg_info;
+
+            if (fn->common.fn_flags & ZEND_ACC_VARIADIC) {
+                num_args++;
+            }
+            arg_info = malloc(sizeof(zend_internal_arg_info) * num_args);
+            memcpy(arg_info, fn->internal_function.arg_info - 1,
+                   sizeof(zend_internal_arg_info) * num_args);
+            new_fn->internal_function.arg_info = arg_info + 1;
+        }
+#endif
+
+        if (new_fn->common.fn_flags & ZEND_ACC_ABSTRACT) {
+            ce->ce_flags |= ZEND_ACC_IMPLICIT_ABSTRACT_CLASS;
+        }
+
+        // The inherited method is a copy owned by the class, so it is released
+        // by the destructor of the function table.
+        zend_hash_update_ptr(&ce->function_table, key, new_fn);
+
+#if PHP_VERSION_ID >= 80000
+        zend_add_magic_method(ce, new_fn, key);
+#else
+        phper_zend_add_magic_method(ce, new_fn, key);
+#endif
+    }
+    ZEND_HASH_FOREACH_END();
+
+    ZEND_HASH_FOREACH_STR_KEY_PTR(&trait->properties_info, key, prop) {
+        uint32_t flags = prop->flags & (ZEND_ACC_PPP_MASK | ZEND_ACC_STATIC);
+        zval *default_value;
+        zval value;
+
+        if (zend_hash_exists(&ce->properties_info, key)) {
+            continue;
+        }
+
+#if PHP_VERSION_ID >= 80100
+        flags |= prop->flags & ZEND_ACC_READONLY;
+#endif
+
+        if (prop->flags & ZEND_ACC_STATIC) {
+            default_value = &trait->default_static_members_table[prop->offset];
+        } else {
+            default_value =
+                &trait->default_properties_table[OBJ_PROP_TO_NUM(prop->offset)];
+        }
+        ZVAL_DEREF(default_value);
+        ZVAL_COPY(&value, default_value);
+
+#if PHP_VERSION_ID >= 80000
+        zend_property_info *new_prop = zend_declare_typed_property(
+            ce, key, &value, flags, NULL, prop->type);
+        if (prop->attributes) {
+            new_prop->attributes = prop->attributes;
+            GC_ADDREF(new_prop->attributes);
+        }
+#elif PHP_VERSION_ID >= 70400
+        zend_declare_typed_property(ce, key, &value, flags, NULL, prop->type);
+#else
+        zend_declare_property_ex(ce, key, &value, flags, NULL);
+#endif
+    }
+    ZEND_HASH_FOREACH_END();
+}
+
+// ==================================================
+// enum apis:
+// ==================================================
+
+#if PHP_VERSION_ID >= 80100
+zend_object *phper_zend_enum_find_case(zend_class_entry *ce, const char *name,
+                                       size_t name_len) {
+    if (!(ce->ce_flags & ZEND_ACC_ENUM)) {
+        return NULL;
+    }
+
+    zend_class_constant *c =
+        zend_hash_str_find_ptr(CE_CONSTANTS_TABLE(ce), name, name_len);
+    if (c == NULL || !(ZEND_CLASS_CONST_FLAGS(c) & ZEND_CLASS_CONST_IS_CASE)) {
+        return NULL;
+    }
+
+    if (Z_TYPE(c->value) == IS_CONSTANT_AST &&
+        zval_update_constant_ex(&c->value, c->ce) == FAILURE) {
+        return NULL;
+    }
+    return Z_TYPE(c->value) == IS_OBJECT ? Z_OBJ(c->value) : NULL;
+}
+
+zend_object *phper_zend_enum_find_case_by_value(zend_class_entry *ce,
+                                                const zval *value) {
+    if (!(ce->ce_flags & ZEND_ACC_ENUM) ||
+        ce->enum_backing_type == IS_UNDEF) {
+        return NULL;
+    }
+
+    if (ce->type == ZEND_USER_CLASS &&
+        !(ce->ce_flags & ZEND_ACC_CONSTANTS_UPDATED) &&
+        zend_update_class_constants(ce) == FAILURE) {
+        return NULL;
+    }
+
+    HashTable *backed_enum_table = CE_BACKED_ENUM_TABLE(ce);
+    if (backed_enum_table == NULL) {
+        return NULL;
+    }
+
+    zval *case_name = NULL;
+    if (ce->enum_backing_type == IS_LONG && Z_TYPE_P(value) == IS_LONG) {
+        case_name = zend_hash_index_find(backed_enum_table, Z_LVAL_P(value));
+    } else if (ce->enum_backing_type == IS_STRING &&
+               Z_TYPE_P(value) == IS_STRING) {
+        case_name = zend_hash_find(backed_enum_table, Z_STR_P(value));
+    }
+    if (case_name == NULL) {
+        return NULL;
+    }
+
+    return phper_zend_enum_find_case(ce, Z_STRVAL_P(case_name),
+                                     Z_STRLEN_P(case_name));
+}
+
+void phper_zend_enum_cases(zend_class_entry *ce, zval *return_value) {
+    zend_class_constant *c;
+
+    array_init(return_value);
+    ZEND_HASH_FOREACH_PTR(CE_CONSTANTS_TABLE(ce), c) {
+        if (!(ZEND_CLASS_CONST_FLAGS(c) & ZEND_CLASS_CONST_IS_CASE)) {
+            continue;
+        }
+        if (Z_TYPE(c->value) == IS_CONSTANT_AST &&
+            zval_update_constant_ex(&c->value, c->ce) == FAILURE) {
+            continue;
+        }
+        Z_ADDREF(c->value);
+        add_next_index_zval(return_value, &c->value);
+    }
+    ZEND_HASH_FOREACH_END();
+}
+
+zend_string *phper_zend_enum_fetch_case_name(zend_object *zobj) {
+    return Z_STR_P(zend_enum_fetch_case_name(zobj));
+}
+
+zval *phper_zend_enum_fetch_case_value(zend_object *zobj) {
+    if (zobj->ce->enum_backing_type == IS_UNDEF) {
+        return NULL;
+    }
+    return zend_enum_fetch_case_value(zobj);
+}
+#endif
+
+// ==================================================
+// fiber apis:
+// ==================================================
+
+#if PHP_VERSION_ID >= 80100
+zend_object *phper_zend_fiber_active(void) {
+    zend_fiber *fiber = EG(active_fiber);
+    return fiber == NULL ? NULL : &fiber->std;
+}
+
+bool phper_zend_fiber_is_started(const zend_object *obj) {
+    const zend_fiber *fiber = (const zend_fiber *)obj;
+    return fiber->context.status != ZEND_FIBER_STATUS_INIT;
+}
+
+bool phper_zend_fiber_is_suspended(const zend_object *obj) {
+    const zend_fiber *fiber = (const zend_fiber *)obj;
+    return fiber->context.status == ZEND_FIBER_STATUS_SUSPENDED &&
+           fiber->caller == NULL;
+}
+
+bool phper_zend_fiber_is_running(const zend_object *obj) {
+    const zend_fiber *fiber = (const zend_fiber *)obj;
+    return fiber->context.status == ZEND_FIBER_STATUS_RUNNING ||
+           fiber->caller != NULL;
+}
+
+bool phper_zend_fiber_is_terminated(const zend_object *obj) {
+    const zend_fiber *fiber = (const zend_fiber *)obj;
+    return fiber->context.status == ZEND_FIBER_STATUS_DEAD;
+}
+#endif
+
+// ==================================================
+// lazy object apis:
+// ==================================================
+
+#if PHP_VERSION_ID >= 80400
+ZEND_BEGIN_ARG_INFO_EX(phper_lazy_initializer_arg_info, 0, 0, 1)
+ZEND_ARG_INFO(0, object)
+ZEND_END_ARG_INFO()
+
+// The function wrapped by the initializer closures, the handler finds the
+// initializer by the object passed in.
+static zend_internal_function phper_lazy_initializer_function;
+
+// Initialize the function once in `MINIT`, so it's shared by the threads.
+void phper_zend_lazy_objects_startup(zif_handler handler) {
+    zend_internal_function *func = &phper_lazy_initializer_function;
+    func->type = ZEND_INTERNAL_FUNCTION;
+    func->function_name = ZSTR_KNOWN(ZEND_STR_MAGIC_INVOKE);
+    func->handler = handler;
+    func->num_args = 1;
+    func->required_num_args = 1;
+    func->arg_info =
+        (zend_internal_arg_info *)phper_lazy_initializer_arg_info + 1;
+}
+
+zend_object *phper_zend_object_make_lazy(zend_class_entry *ce, bool proxy) {
+    zval initializer;
+    zend_create_closure(&initializer,
+                        (zend_function *)&phper_lazy_initializer_function, NULL,
+                        NULL, NULL);
+
+    zend_fcall_info_cache fcc;
+    zend_object *obj = NULL;
+    if (zend_is_callable_ex(&initializer, NULL, 0, NULL, &fcc, NULL)) {
+        obj = zend_object_make_lazy(NULL, ce, &initializer, &fcc,
+                                    proxy ? ZEND_LAZY_OBJECT_STRATEGY_PROXY
+                                          : ZEND_LAZY_OBJECT_STRATEGY_GHOST);
+    }
+
+    zval_ptr_dtor(&initializer);
+    return obj;
+}
+
+bool phper_zend_lazy_object_initialized(const zend_object *obj) {
+    return !zend_object_is_lazy((zend_object *)obj) ||
+           zend_lazy_object_initialized((zend_object *)obj);
+}
+
+void phper_zend_lazy_object_init(zend_object *obj) {
+    if (!phper_zend_lazy_object_initialized(obj)) {
+        zend_lazy_object_init(obj);
+    }
+}
+#endif
+
 // ==================================================
 // function apis:
 // ==================================================
@@ -514,6 +1197,182 @@ const char *phper_get_zend_module_build_id() {
     return ZEND_MODULE_BUILD_ID;
 }
 
+// ==================================================
+// ini apis:
+// ==================================================
+
+zend_long phper_zend_ini_parse_quantity(const char *value, size_t len,
+                                        bool *valid) {
+#if PHP_VERSION_ID >= 80200
+    zend_string *str = zend_string_init(value, len, 0);
+    zend_string *errstr = NULL;
+    zend_long result = zend_ini_parse_quantity(str, &errstr);
+    zend_string_release(str);
+    *valid = errstr == NULL;
+    if (errstr != NULL) {
+        zend_string_release(errstr);
+    }
+    return result;
+#else
+    *valid = true;
+    return zend_atol(value, len);
+#endif
+}
+
+// ==================================================
+// constant apis:
+// ==================================================
+
+void phper_zend_register_zval_constant(const char *name, size_t len,
+                                       zval *value, int flags,
+                                       int module_number) {
+    zend_constant c;
+    ZVAL_COPY_VALUE(&c.value, value);
+#if PHP_VERSION_ID >= 70300
+    ZEND_CONSTANT_SET_FLAGS(&c, flags, module_number);
+    c.name = zend_string_init_interned(name, len, flags & CONST_PERSISTENT);
+#else
+    c.flags = flags;
+    c.module_number = module_number;
+    c.name = zend_string_init(name, len, flags & CONST_PERSISTENT);
+#endif
+    zend_register_constant(&c);
+}
+
+#if PHP_VERSION_ID >= 80500
+void phper_zend_constant_add_attribute(const char *name, size_t len,
+                                       const char *attr, size_t attr_len) {
+    zend_constant *c = zend_hash_str_find_ptr(EG(zend_constants), name, len);
+    if (c == NULL) {
+        return;
+    }
+    zend_string *attr_name = zend_string_init_interned(attr, attr_len, 1);
+    zend_add_attribute(&c->attributes, attr_name, 0, ZEND_ATTRIBUTE_PERSISTENT,
+                       0, 0);
+    zend_string_release(attr_name);
+}
+#endif
+
+// ==================================================
+// attribute apis:
+// ==================================================
+
+#if PHP_MAJOR_VERSION >= 8
+zend_attribute *phper_zend_add_attribute(HashTable **attributes,
+                                         const char *name, size_t name_len,
+                                         uint32_t argc, uint32_t offset) {
+    zend_string *name_str = zend_string_init_interned(name, name_len, 1);
+#if PHP_VERSION_ID >= 80100
+    zend_attribute *attr = zend_add_attribute(
+        attributes, name_str, argc, ZEND_ATTRIBUTE_PERSISTENT, offset, 0);
+#else
+    zend_attribute *attr =
+        zend_add_attribute(attributes, 1, offset, name_str, argc);
+#endif
+    zend_string_release(name_str);
+    return attr;
+}
+
+void phper_zend_attribute_set_argument(zend_attribute *attr, uint32_t index,
+                                       const char *name, size_t name_len,
+                                       zval *value) {
+    attr->args[index].name =
+        name == NULL ? NULL : zend_string_init_interned(name, name_len, 1);
+    ZVAL_COPY_VALUE(&attr->args[index].value, value);
+}
+#endif
+
+// ==================================================
+// executor apis:
+// ==================================================
+
+bool phper_zend_vm_interrupted(void) {
+#if PHP_VERSION_ID >= 80200
+    return zend_atomic_bool_load_ex(&EG(vm_interrupt));
+#elif PHP_VERSION_ID >= 70100
+    return EG(vm_interrupt);
+#else
+    return false;
+#endif
+}
+
+bool phper_zend_timed_out(void) {
+#if PHP_VERSION_ID >= 80200
+    return zend_atomic_bool_load_ex(&EG(timed_out));
+#elif PHP_VERSION_ID >= 70100
+    return EG(timed_out);
+#else
+    return false;
+#endif
+}
+
+// Handle the interrupt which isn't the timeout, like the VM does, such as
+// calling the signal handlers registered by `pcntl`.
+void phper_zend_interrupt(void) {
+#if PHP_VERSION_ID >= 80200
+    zend_atomic_bool_store_ex(&EG(vm_interrupt), false);
+#elif PHP_VERSION_ID >= 70100
+    EG(vm_interrupt) = 0;
+#endif
+#if PHP_VERSION_ID >= 70100
+    if (zend_interrupt_function) {
+        zend_interrupt_function(EG(current_execute_data));
+    }
+#endif
+}
+
+// ==================================================
+// globals apis:
+// ==================================================
+
+zval *phper_zend_global_var_find(const char *name, size_t len) {
+    zval *var = zend_hash_str_find_ind(&EG(symbol_table), name, len);
+    if (var != NULL) {
+        ZVAL_DEREF(var);
+    }
+    return var;
+}
+
+void phper_zend_global_var_update(const char *name, size_t len, zval *value) {
+    zval *var = zend_hash_str_find_ind(&EG(symbol_table), name, len);
+    if (var == NULL) {
+        zend_hash_str_update_ind(&EG(symbol_table), name, len, value);
+        return;
+    }
+    ZVAL_DEREF(var);
+    zval garbage;
+    ZVAL_COPY_VALUE(&garbage, var);
+    ZVAL_COPY_VALUE(var, value);
+    zval_ptr_dtor(&garbage);
+}
+
+bool phper_zend_is_auto_global_str(const char *name, size_t len) {
+    return zend_is_auto_global_str((char *)name, len);
+}
+
+// ==================================================
+// sapi apis:
+// ==================================================
+
+zend_string *phper_sapi_read_request_body() {
+    php_stream *stream =
+        php_stream_open_wrapper("php://input", "rb", 0, NULL);
+    if (stream == NULL) {
+        return NULL;
+    }
+    zend_string *body = php_stream_copy_to_mem(stream, PHP_STREAM_COPY_ALL, 0);
+    php_stream_close(stream);
+    return body;
+}
+
+// ==================================================
+// zend extension apis:
+// ==================================================
+
+const char *phper_get_zend_extension_build_id() {
+    return ZEND_EXTENSION_BUILD_ID;
+}
+
 zend_internal_arg_info
 phper_zend_begin_arg_info_ex(bool return_reference,
                              uintptr_t required_num_args) {
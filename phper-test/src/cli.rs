@@ -10,7 +10,7 @@
 
 //! Test tools for php cli program.
 
-use crate::context::Context;
+use crate::context::{Context, ContextCommand};
 use log::debug;
 use std::{
     panic::{UnwindSafe, catch_unwind, resume_unwind},
@@ -146,43 +146,81 @@ pub fn test_php_scripts_with_condition(
     let context = Context::get_global();
 
     for (script, condition) in scripts {
-        let mut cmd = context.create_command_with_lib(&lib_path, script);
+        let cmd = context.create_command_with_lib(&lib_path, script);
+        check_command(cmd, script, condition);
+    }
+}
+
+/// Check the php script executed with the custom ini entries passed by `-d`,
+/// instead of loading the single extension by `extension=`.
+///
+/// It's useful for loading the library by `zend_extension=`, or loading
+/// several extensions in the specified order.
+///
+/// # Panics
+///
+/// Panics if the script execution fails or the condition returns false.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use phper_test::cli::test_php_script_with_ini;
+///
+/// test_php_script_with_ini(
+///     &["zend_extension=/path/to/extension.so"],
+///     "test.php",
+///     |output| output.status.success(),
+/// );
+/// ```
+pub fn test_php_script_with_ini(
+    ini_entries: &[&str], script: impl AsRef<Path>, condition: impl Fn(Output) -> bool,
+) {
+    let context = Context::get_global();
+    let ini_entries = ini_entries
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect::<Vec<_>>();
+    let cmd = context.create_command_with_ini(&ini_entries, &script);
+    check_command(cmd, &script, &condition);
+}
 
-        let output = cmd.output().unwrap();
-        let path = script.as_ref().to_str().unwrap();
+fn check_command(
+    mut cmd: ContextCommand, script: &dyn AsRef<Path>, condition: &dyn Fn(Output) -> bool,
+) {
+    let output = cmd.output().unwrap();
+    let path = script.as_ref().to_str().unwrap();
 
-        let mut stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        if stdout.is_empty() {
-            stdout.push_str("<empty>");
-        }
+    let mut stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if stdout.is_empty() {
+        stdout.push_str("<empty>");
+    }
 
-        let mut stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        if stderr.is_empty() {
-            stderr.push_str("<empty>");
-        };
+    let mut stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if stderr.is_empty() {
+        stderr.push_str("<empty>");
+    };
 
-        debug!(command:% = cmd.get_command().join(" ".as_ref()).to_string_lossy(),
-               status:? = output.status.code(),
-               stdout = &*stdout,
-               stderr:%,
-               signal:? = {
-                   #[cfg(unix)]
-                   {
-                       use std::os::unix::process::ExitStatusExt as _;
-                       output.status.signal()
-                   }
-                   #[cfg(not(unix))]
-                   {
-                       None
-                   }
-               };
-               "execute php test command");
+    debug!(command:% = cmd.get_command().join(" ".as_ref()).to_string_lossy(),
+           status:? = output.status.code(),
+           stdout = &*stdout,
+           stderr:%,
+           signal:? = {
+               #[cfg(unix)]
+               {
+                   use std::os::unix::process::ExitStatusExt as _;
+                   output.status.signal()
+               }
+               #[cfg(not(unix))]
+               {
+                   None
+               }
+           };
+           "execute php test command");
 
-        if !condition(output) {
-            eprintln!("--- stdout ---\n{}", stdout);
-            eprintln!("--- stderr ---\n{}", stderr);
-            panic!("test php file `{}` failed", path);
-        }
+    if !condition(output) {
+        eprintln!("--- stdout ---\n{}", stdout);
+        eprintln!("--- stderr ---\n{}", stderr);
+        panic!("test php file `{}` failed", path);
     }
 }
 
@@ -71,14 +71,23 @@ impl Context {
 
     pub fn create_command_with_lib(
         &self, lib_path: impl AsRef<Path>, script: impl AsRef<Path>,
+    ) -> ContextCommand {
+        self.create_command_with_ini(
+            &[format!("extension={}", lib_path.as_ref().display())],
+            script,
+        )
+    }
+
+    pub fn create_command_with_ini(
+        &self, ini_entries: &[String], script: impl AsRef<Path>,
     ) -> ContextCommand {
         let mut cmd = Command::new(&self.php_bin);
-        let args = vec![
-            "-n".to_owned(),
-            "-d".to_owned(),
-            format!("extension={}", lib_path.as_ref().display()),
-            script.as_ref().display().to_string(),
-        ];
+        let mut args = vec!["-n".to_owned()];
+        for entry in ini_entries {
+            args.push("-d".to_owned());
+            args.push(entry.clone());
+        }
+        args.push(script.as_ref().display().to_string());
         cmd.args(&args);
         ContextCommand { cmd }
     }
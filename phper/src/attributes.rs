@@ -0,0 +1,124 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to [zend_attribute], only supported since PHP 8.0.
+#![cfg(phper_major_version = "8")]
+
+use crate::{sys::*, types::Scalar};
+use std::ptr::null;
+
+/// Attribute with constant arguments, added to the functions, classes,
+/// methods, properties and parameters registered by phper, which can be read
+/// by `Reflection*::getAttributes()`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{attributes::Attribute, functions::Argument, modules::Module};
+///
+/// let mut module = Module::new("hello", "0.1.0", "");
+///
+/// module
+///     .add_function("login", |_| phper::ok(()))
+///     .argument(Argument::new("password").with_attribute("SensitiveParameter"))
+///     .add_attribute(Attribute::new("Framework\\Route").argument("/login"));
+/// ```
+pub struct Attribute {
+    name: String,
+    arguments: Vec<(Option<String>, Scalar)>,
+}
+
+impl Attribute {
+    /// Construct a new `Attribute` with the attribute class name, the leading
+    /// backslash is optional.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            name: name
+                .strip_prefix('\\')
+                .map(ToOwned::to_owned)
+                .unwrap_or(name),
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Add positional argument.
+    pub fn argument(mut self, value: impl Into<Scalar>) -> Self {
+        self.arguments.push((None, value.into()));
+        self
+    }
+
+    /// Add named argument.
+    pub fn named_argument(mut self, name: impl Into<String>, value: impl Into<Scalar>) -> Self {
+        self.arguments.push((Some(name.into()), value.into()));
+        self
+    }
+
+    /// Add the attribute to the attributes table, the `offset` is `0` for the
+    /// item itself, and `n + 1` for the `n`th parameter.
+    pub(crate) unsafe fn add_to(&self, attributes: *mut *mut HashTable, offset: u32) {
+        unsafe {
+            let attr = phper_zend_add_attribute(
+                attributes,
+                self.name.as_ptr().cast(),
+                self.name.len(),
+                self.arguments.len().try_into().unwrap(),
+                offset,
+            );
+            for (index, (name, value)) in self.arguments.iter().enumerate() {
+                let mut value = value.to_persistent_zval();
+                let (name_ptr, name_len) = match name {
+                    Some(name) => (name.as_ptr().cast(), name.len()),
+                    None => (null(), 0),
+                };
+                phper_zend_attribute_set_argument(
+                    attr,
+                    index.try_into().unwrap(),
+                    name_ptr,
+                    name_len,
+                    &mut value,
+                );
+            }
+        }
+    }
+}
+
+impl From<&str> for Attribute {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Attribute {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// The targets of the attribute class, see
+/// [ClassEntity::set_attribute_class](crate::classes::ClassEntity::set_attribute_class).
+#[repr(u32)]
+#[derive(Copy, Clone)]
+pub enum AttributeTarget {
+    /// `Attribute::TARGET_CLASS`.
+    Class = ZEND_ATTRIBUTE_TARGET_CLASS,
+    /// `Attribute::TARGET_FUNCTION`.
+    Function = ZEND_ATTRIBUTE_TARGET_FUNCTION,
+    /// `Attribute::TARGET_METHOD`.
+    Method = ZEND_ATTRIBUTE_TARGET_METHOD,
+    /// `Attribute::TARGET_PROPERTY`.
+    Property = ZEND_ATTRIBUTE_TARGET_PROPERTY,
+    /// `Attribute::TARGET_CLASS_CONSTANT`.
+    ClassConstant = ZEND_ATTRIBUTE_TARGET_CLASS_CONST,
+    /// `Attribute::TARGET_PARAMETER`.
+    Parameter = ZEND_ATTRIBUTE_TARGET_PARAMETER,
+    /// `Attribute::TARGET_ALL`.
+    All = ZEND_ATTRIBUTE_TARGET_ALL,
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the debug backtrace, like PHP `debug_backtrace()`.
+
+use crate::{
+    sys::*,
+    values::{ExecuteData, ZVal},
+};
+
+/// The way the function of the frame is called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallType {
+    /// Called on an object, like `$obj->foo()`.
+    Object,
+    /// Called statically, like `Foo::foo()`.
+    Static,
+}
+
+/// A frame of the backtrace.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    function: String,
+    class: Option<String>,
+    call_type: Option<CallType>,
+    file: Option<String>,
+    line: Option<u32>,
+    args: Option<Vec<ZVal>>,
+}
+
+impl Frame {
+    /// Gets the function or method name.
+    #[inline]
+    pub fn function(&self) -> &str {
+        &self.function
+    }
+
+    /// Gets the class name if the frame is a method call.
+    #[inline]
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_deref()
+    }
+
+    /// Gets the call type if the frame is a method call.
+    #[inline]
+    pub fn call_type(&self) -> Option<CallType> {
+        self.call_type
+    }
+
+    /// Gets the file where the function is called, none if the function is
+    /// called by an internal function.
+    #[inline]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Gets the line where the function is called, none if the function is
+    /// called by an internal function.
+    #[inline]
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Gets the arguments, only collected when required.
+    #[inline]
+    pub fn args(&self) -> Option<&[ZVal]> {
+        self.args.as_deref()
+    }
+}
+
+/// Gets the backtrace of the current executing frame, the first frame is the
+/// innermost one, that is the function calling `backtrace` if called in a
+/// handler.
+///
+/// The frames of the main script and `include`/`require` are skipped.
+pub fn backtrace(with_args: bool) -> Vec<Frame> {
+    unsafe {
+        match ExecuteData::try_from_ptr(crate::eg!(current_execute_data)) {
+            Some(execute_data) => execute_data.backtrace(with_args),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl ExecuteData {
+    /// Gets the backtrace starting from this frame, see [backtrace].
+    pub fn backtrace(&self, with_args: bool) -> Vec<Frame> {
+        let mut frames = Vec::new();
+        let mut current = Some(self);
+        while let Some(execute_data) = current {
+            if let Some(frame) = unsafe { Frame::new(execute_data, with_args) } {
+                frames.push(frame);
+            }
+            current = execute_data.prev();
+        }
+        frames
+    }
+}
+
+impl Frame {
+    unsafe fn new(execute_data: &ExecuteData, with_args: bool) -> Option<Self> {
+        unsafe {
+            let raw = execute_data.as_ptr();
+            if (*raw).func.is_null() {
+                return None;
+            }
+
+            let func = execute_data.func();
+            let function = func.get_function_name()?.to_string_lossy().into_owned();
+            let class = func
+                .get_class()
+                .map(|cls| cls.get_name().to_string_lossy().into_owned());
+            let call_type = class.as_ref().map(|_| {
+                if ZVal::from_ptr(&(*raw).This).get_type_info().is_object() {
+                    CallType::Object
+                } else {
+                    CallType::Static
+                }
+            });
+
+            let (file, line) = match execute_data.prev() {
+                Some(caller)
+                    if !(*caller.as_ptr()).func.is_null()
+                        && caller.func().get_type() != ZEND_INTERNAL_FUNCTION =>
+                {
+                    (
+                        caller
+                            .func()
+                            .get_filename()
+                            .map(|f| f.to_string_lossy().into_owned()),
+                        caller.get_opline_lineno(),
+                    )
+                }
+                _ => (None, None),
+            };
+
+            let args = with_args.then(|| collect_args(execute_data));
+
+            Some(Self {
+                function,
+                class,
+                call_type,
+                file,
+                line,
+                args,
+            })
+        }
+    }
+}
+
+/// Collects the passed arguments, the extra arguments of user function are
+/// stored after the compiled and temporary variables.
+#[allow(clippy::useless_conversion)]
+unsafe fn collect_args(execute_data: &ExecuteData) -> Vec<ZVal> {
+    unsafe {
+        let raw = execute_data.as_ptr() as *mut zend_execute_data;
+        let func = (*raw).func;
+        let num_args = execute_data.num_args();
+
+        let (first_extra_arg, extra_offset) = if u32::from((*func).type_) == ZEND_INTERNAL_FUNCTION
+        {
+            (num_args, 0)
+        } else {
+            let op_array = &(*func).op_array;
+            (
+                op_array.num_args as usize,
+                (op_array.last_var + op_array.T) as usize,
+            )
+        };
+
+        (0..num_args)
+            .map(|i| {
+                let index = if i < first_extra_arg {
+                    i
+                } else {
+                    extra_offset + i - first_extra_arg
+                };
+                let val = ZVal::from_ptr(phper_zend_call_var_num(raw, index.try_into().unwrap()));
+                if val.get_type_info().is_undef() {
+                    ZVal::default()
+                } else {
+                    val.clone()
+                }
+            })
+            .collect()
+    }
+}
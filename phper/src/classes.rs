@@ -10,21 +10,27 @@
 
 //! Apis relate to [zend_class_entry].
 
+#[cfg(phper_major_version = "8")]
+use crate::attributes::{Attribute, AttributeTarget};
 use crate::{
     arrays::ZArr,
+    constants,
     errors::{ClassNotFoundError, InitializeObjectError, Throwable},
-    functions::{Function, FunctionEntry, HandlerMap, Method, MethodEntity},
+    functions::{
+        Argument, Callable, Function, FunctionEntry, HandlerMap, Method, MethodEntity,
+        TraitHandlerMap, TraitMethod, call_raw_common,
+    },
     modules::global_module,
-    objects::{StateObj, StateObject, ZObject},
+    objects::{StateObj, StateObject, ZObj, ZObject},
     strings::ZStr,
     sys::*,
-    types::Scalar,
+    types::{ArgumentTypeHint, Scalar},
     utils::ensure_end_with_zero,
     values::ZVal,
 };
 use std::{
     any::Any,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     ffi::{CString, c_char, c_void},
     fmt::Debug,
     marker::PhantomData,
@@ -137,6 +143,7 @@ impl ClassEntry {
     ///
     /// **Be careful when `__construct` is necessary.**
     pub fn init_object(&self) -> crate::Result<ZObject> {
+        self.check_instantiable()?;
         unsafe {
             let ptr = self.as_ptr() as *mut _;
             let mut val = ZVal::default();
@@ -152,6 +159,69 @@ impl ClassEntry {
         }
     }
 
+    /// Create the object from class and initialize the properties from the
+    /// array, without calling `__construct`, useful for hydration.
+    ///
+    /// The keys are the names of properties, the private and protected
+    /// properties declared by the class can be given by plain names, and the
+    /// private properties of the parent classes should be given by the
+    /// mangled names (like the keys of `(array) $object`). The values of the
+    /// typed properties are verified in strict mode, the `TypeError` is
+    /// returned if failed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{arrays::ZArray, classes::ClassEntry};
+    ///
+    /// fn hydrate_user() -> phper::Result<()> {
+    ///     let mut properties = ZArray::new();
+    ///     properties.insert("id", 1);
+    ///     properties.insert("name", "foo");
+    ///     let _user = ClassEntry::from_globals("User")?.instantiate_with_properties(&properties)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn instantiate_with_properties(&self, properties: &ZArr) -> crate::Result<ZObject> {
+        self.check_instantiable()?;
+        let mut initialized = false;
+        let val = call_raw_common(|val| unsafe {
+            initialized = phper_object_init_with_properties(
+                val.as_mut_ptr(),
+                self.as_ptr() as *mut _,
+                properties.as_ptr() as *mut _,
+            );
+        })?;
+        if !initialized {
+            return Err(InitializeObjectError::new(self.get_name().to_str()?.to_owned()).into());
+        }
+        unsafe {
+            // The object is owned by the returned `ZObject`.
+            let mut val = ManuallyDrop::new(val);
+            let ptr = phper_z_obj_p(val.as_mut_ptr());
+            Ok(ZObject::from_raw_cast(ptr))
+        }
+    }
+
+    /// Check whether the class can be instantiated, the abstract classes,
+    /// interfaces, traits and enums can't.
+    pub(crate) fn check_instantiable(&self) -> crate::Result<()> {
+        let flags = self.inner.ce_flags;
+        let kind = if flags & ZEND_ACC_INTERFACE != 0 {
+            "interface"
+        } else if flags & ZEND_ACC_TRAIT != 0 {
+            "trait"
+        } else if is_enum_flags(flags) {
+            "enum"
+        } else if flags & (ZEND_ACC_IMPLICIT_ABSTRACT_CLASS | ZEND_ACC_EXPLICIT_ABSTRACT_CLASS) != 0
+        {
+            "abstract class"
+        } else {
+            return Ok(());
+        };
+        Err(InitializeObjectError::with_kind(kind, self.get_name().to_str()?.to_owned()).into())
+    }
+
     /// Get the class name.
     pub fn get_name(&self) -> &ZStr {
         unsafe { ZStr::from_ptr(self.inner.name) }
@@ -170,6 +240,17 @@ impl ClassEntry {
         unsafe { phper_instanceof_function(self.as_ptr(), parent.as_ptr()) }
     }
 
+    /// Get the constant value by name of class, the private and protected
+    /// constants of this class are accessible.
+    ///
+    /// Return None when the constant isn't defined.
+    pub fn get_constant(&self, name: impl AsRef<str>) -> Option<ZVal> {
+        let mut full_name = self.get_name().to_bytes().to_vec();
+        full_name.extend_from_slice(b"::");
+        full_name.extend_from_slice(name.as_ref().as_bytes());
+        constants::get_with_scope(&full_name, self.as_ptr() as *mut _)
+    }
+
     /// Get the static property by name of class.
     ///
     /// Return None when static property hasn't register by
@@ -347,6 +428,16 @@ impl<T: 'static> StateClass<T> {
             .map(ZObject::into_raw_cast)
             .map(StateObject::<T>::from_raw_object)
     }
+
+    /// Create the object from class and initialize the properties from the
+    /// array, without calling `__construct`, see
+    /// [ClassEntry::instantiate_with_properties].
+    pub fn instantiate_with_properties(&self, properties: &ZArr) -> crate::Result<StateObject<T>> {
+        self.as_class_entry()
+            .instantiate_with_properties(properties)
+            .map(ZObject::into_raw_cast)
+            .map(StateObject::<T>::from_raw_object)
+    }
 }
 
 impl<T> Clone for StateClass<T> {
@@ -437,6 +528,56 @@ impl Interface {
     }
 }
 
+/// The [Trait] holds [zend_class_entry], created by
+/// [Module::add_trait](crate::modules::Module::add_trait).
+///
+/// Like [Interface], it is initialized when the trait registered (module
+/// initialized), so you shouldn't use it in `module_init` stage.
+#[derive(Clone)]
+pub struct Trait {
+    inner: Rc<RefCell<InnerClassEntry>>,
+}
+
+impl Trait {
+    fn null() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(InnerClassEntry::Ptr(null()))),
+        }
+    }
+
+    /// Create a new trait from global name, the trait should be registered by
+    /// other extensions.
+    pub fn from_name(name: impl Into<String>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(InnerClassEntry::Name(name.into()))),
+        }
+    }
+
+    fn bind(&self, ptr: *mut zend_class_entry) {
+        match &mut *self.inner.borrow_mut() {
+            InnerClassEntry::Ptr(p) => {
+                *p = ptr;
+            }
+            InnerClassEntry::Name(_) => {
+                unreachable!("Cannot bind() a Trait created with from_name()");
+            }
+        }
+    }
+
+    /// Converts to class entry.
+    pub fn as_class_entry(&self) -> &ClassEntry {
+        let inner = self.inner.borrow().clone();
+        match inner {
+            InnerClassEntry::Ptr(ptr) => unsafe { ClassEntry::from_ptr(ptr) },
+            InnerClassEntry::Name(name) => {
+                let entry = ClassEntry::from_globals(name).unwrap();
+                *self.inner.borrow_mut() = InnerClassEntry::Ptr(entry.as_ptr());
+                entry
+            }
+        }
+    }
+}
+
 pub(crate) type StateConstructor = dyn Fn() -> *mut dyn Any;
 
 pub(crate) type StateCloner = dyn Fn(*const dyn Any) -> *mut dyn Any;
@@ -451,12 +592,20 @@ pub struct ClassEntity<T: 'static> {
     class_name: CString,
     state_constructor: Rc<StateConstructor>,
     method_entities: Vec<MethodEntity>,
+    magic_method_entities: Vec<MethodEntity>,
+    hidden_function_table: Cell<*mut HashTable>,
     property_entities: Vec<PropertyEntity>,
     parent: Option<StateClass<[()]>>,
     interfaces: Vec<Interface>,
+    traits: Vec<Trait>,
     constants: Vec<ConstantEntity>,
     bound_class: StateClass<T>,
     state_cloner: Option<Rc<StateCloner>>,
+    flags: u32,
+    #[cfg(phper_major_version = "8")]
+    attributes: Vec<Attribute>,
+    #[cfg(phper_major_version = "8")]
+    attribute_flags: Option<u32>,
     _p: PhantomData<(*mut (), T)>,
 }
 
@@ -489,12 +638,20 @@ impl<T: 'static> ClassEntity<T> {
                 Box::into_raw(boxed)
             }),
             method_entities: Vec::new(),
+            magic_method_entities: Vec::new(),
+            hidden_function_table: Cell::new(null_mut()),
             property_entities: Vec::new(),
             parent: None,
             interfaces: Vec::new(),
+            traits: Vec::new(),
             constants: Vec::new(),
             bound_class: StateClass::null(),
             state_cloner: None,
+            flags: 0,
+            #[cfg(phper_major_version = "8")]
+            attributes: Vec::new(),
+            #[cfg(phper_major_version = "8")]
+            attribute_flags: None,
             _p: PhantomData,
         }
     }
@@ -541,6 +698,182 @@ impl<T: 'static> ClassEntity<T> {
         self.method_entities.last_mut().unwrap()
     }
 
+    /// Add abstract static method to class, with visibility (shouldn't be
+    /// private).
+    pub fn add_abstract_static_method(
+        &mut self, name: impl Into<String>, vis: Visibility,
+    ) -> &mut MethodEntity {
+        let mut entity = MethodEntity::new(name, None, vis);
+        entity.set_vis_abstract();
+        entity.set_vis_static();
+        self.method_entities.push(entity);
+        self.method_entities.last_mut().unwrap()
+    }
+
+    /// Handle the reading of inaccessible or undefined properties, like
+    /// `__get`, the handler receives the property name.
+    ///
+    /// The magic methods of the `on_*` hooks (except
+    /// [on_invoke](ClassEntity::on_invoke)) are bound to the class directly and
+    /// dispatched by the standard object handlers, the same as the magic
+    /// methods declared in PHP, rather than being added to the methods, so
+    /// they are invisible to userland, such as `method_exists()` and
+    /// reflection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{classes::ClassEntity, values::ZVal};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut class = ClassEntity::<HashMap<String, i64>>::new_with_default_state_constructor("Bag");
+    /// class.on_get(|this, name| {
+    ///     let value = this.as_state().get(name.to_str()?).copied();
+    ///     Ok::<_, phper::Error>(value.map(ZVal::from).unwrap_or_default())
+    /// });
+    /// class.on_set(|this, name, value| {
+    ///     let value = value.expect_long()?;
+    ///     this.as_mut_state().insert(name.to_str()?.to_owned(), value);
+    ///     Ok::<_, phper::Error>(())
+    /// });
+    /// ```
+    pub fn on_get<F, Z, E>(&mut self, handler: F)
+    where
+        F: Fn(&mut StateObj<T>, &ZStr) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__get",
+            [Argument::new("name")],
+            Rc::new(Method::new(
+                move |this: &mut StateObj<T>, arguments: &mut [ZVal]| {
+                    handler(this, magic_name(arguments))
+                },
+            )),
+        );
+    }
+
+    /// Handle the writing of inaccessible or undefined properties, like
+    /// `__set`, the handler receives the property name and the value.
+    pub fn on_set<F, E>(&mut self, handler: F)
+    where
+        F: Fn(&mut StateObj<T>, &ZStr, &mut ZVal) -> Result<(), E> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__set",
+            [Argument::new("name"), Argument::new("value")],
+            Rc::new(Method::new(
+                move |this: &mut StateObj<T>, arguments: &mut [ZVal]| {
+                    let (name, value) = arguments.split_at_mut(1);
+                    handler(this, magic_name(name), &mut value[0])
+                },
+            )),
+        );
+    }
+
+    /// Handle `isset()` or `empty()` on inaccessible or undefined properties,
+    /// like `__isset`, the handler receives the property name.
+    pub fn on_isset<F, E>(&mut self, handler: F)
+    where
+        F: Fn(&mut StateObj<T>, &ZStr) -> Result<bool, E> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__isset",
+            [Argument::new("name")],
+            Rc::new(Method::new(
+                move |this: &mut StateObj<T>, arguments: &mut [ZVal]| {
+                    handler(this, magic_name(arguments))
+                },
+            )),
+        );
+    }
+
+    /// Handle `unset()` on inaccessible or undefined properties, like
+    /// `__unset`, the handler receives the property name.
+    pub fn on_unset<F, E>(&mut self, handler: F)
+    where
+        F: Fn(&mut StateObj<T>, &ZStr) -> Result<(), E> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__unset",
+            [Argument::new("name")],
+            Rc::new(Method::new(
+                move |this: &mut StateObj<T>, arguments: &mut [ZVal]| {
+                    handler(this, magic_name(arguments))
+                },
+            )),
+        );
+    }
+
+    /// Handle the calling of inaccessible or undefined methods, like
+    /// `__call`, the handler receives the method name and the arguments.
+    pub fn on_call<F, Z, E>(&mut self, handler: F)
+    where
+        F: Fn(&mut StateObj<T>, &ZStr, &mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__call",
+            [Argument::new("name"), Argument::new("arguments")],
+            Rc::new(Method::new(
+                move |this: &mut StateObj<T>, arguments: &mut [ZVal]| {
+                    let mut call_arguments = magic_arguments(arguments);
+                    handler(this, magic_name(arguments), &mut call_arguments)
+                },
+            )),
+        );
+    }
+
+    /// Handle the calling of inaccessible or undefined static methods, like
+    /// `__callStatic`, the handler receives the method name and the
+    /// arguments.
+    pub fn on_call_static<F, Z, E>(&mut self, handler: F)
+    where
+        F: Fn(&ZStr, &mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_magic_method(
+            "__callStatic",
+            [Argument::new("name"), Argument::new("arguments")],
+            Rc::new(Function::new(move |arguments: &mut [ZVal]| {
+                let mut call_arguments = magic_arguments(arguments);
+                handler(magic_name(arguments), &mut call_arguments)
+            })),
+        )
+        .set_vis_static();
+    }
+
+    /// Make the object callable, like `__invoke`.
+    ///
+    /// Unlike the other `on_*` hooks, the `__invoke` method is added to the
+    /// methods, because PHP finds it from the method table.
+    pub fn on_invoke<F, Z, E>(&mut self, handler: F) -> &mut MethodEntity
+    where
+        F: Fn(&mut StateObj<T>, &mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        self.add_method("__invoke", Visibility::Public, handler)
+    }
+
+    fn add_magic_method(
+        &mut self, name: &str, arguments: impl IntoIterator<Item = Argument>,
+        handler: Rc<dyn Callable>,
+    ) -> &mut MethodEntity {
+        self.magic_method_entities
+            .retain(|method| method.name.as_bytes() != name.as_bytes());
+        let mut entity = MethodEntity::new(name, Some(handler), Visibility::Public);
+        entity.arguments(arguments);
+        self.magic_method_entities.push(entity);
+        self.magic_method_entities.last_mut().unwrap()
+    }
+
     /// Declare property.
     ///
     /// The argument `value` should be `Copy` because 'zend_declare_property'
@@ -566,6 +899,42 @@ impl<T: 'static> ClassEntity<T> {
         self.property_entities.push(entity);
     }
 
+    /// Declare typed property, which is uninitialized unless the default
+    /// value is set by [PropertyEntity::default_value].
+    ///
+    /// Typed properties are supported since PHP 7.4, the property is declared
+    /// as untyped in the earlier versions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `type_hint` is [ArgumentTypeHint::Callable], which isn't
+    /// allowed for properties.
+    pub fn add_typed_property(
+        &mut self, name: impl Into<String>, visibility: Visibility, type_hint: ArgumentTypeHint,
+    ) -> &mut PropertyEntity {
+        self.property_entities
+            .push(PropertyEntity::new_typed(name, visibility, type_hint));
+        self.property_entities.last_mut().unwrap()
+    }
+
+    /// Declare typed static property.
+    ///
+    /// Typed properties are supported since PHP 7.4, the property is declared
+    /// as untyped in the earlier versions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `type_hint` is [ArgumentTypeHint::Callable], which isn't
+    /// allowed for properties.
+    pub fn add_static_typed_property(
+        &mut self, name: impl Into<String>, visibility: Visibility, type_hint: ArgumentTypeHint,
+    ) -> &mut PropertyEntity {
+        let mut entity = PropertyEntity::new_typed(name, visibility, type_hint);
+        entity.set_vis_static();
+        self.property_entities.push(entity);
+        self.property_entities.last_mut().unwrap()
+    }
+
     /// Add constant to class
     pub fn add_constant(&mut self, name: impl Into<String>, value: impl Into<Scalar>) {
         let constant = ConstantEntity::new(name, value);
@@ -633,6 +1002,94 @@ impl<T: 'static> ClassEntity<T> {
         self.interfaces.push(interface);
     }
 
+    /// Register class to `use` the trait, the methods and properties of the
+    /// trait are copied into the class, except the ones already declared by
+    /// the class. Only the traits of extensions (such as the ones registered
+    /// by [Module::add_trait](crate::modules::Module::add_trait)) can be used,
+    /// because userland traits aren't declared yet in the `MINIT` phase.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{
+    ///     classes::{ClassEntity, TraitEntity, Visibility},
+    ///     modules::Module,
+    /// };
+    ///
+    /// let mut module = Module::new("hello", "0.1.0", "");
+    ///
+    /// let mut greeting = TraitEntity::new("Greeting");
+    /// greeting.add_method("greet", Visibility::Public, |_, _| phper::ok("hello"));
+    /// let greeting = module.add_trait(greeting);
+    ///
+    /// let mut class = ClassEntity::new("MyClass");
+    /// class.uses(greeting);
+    /// module.add_class(class);
+    /// ```
+    pub fn uses(&mut self, r#trait: Trait) {
+        self.traits.push(r#trait);
+    }
+
+    /// Mark the class as `final`, which can't be extended.
+    pub fn set_final(&mut self) -> &mut Self {
+        self.flags |= ZEND_ACC_FINAL;
+        self
+    }
+
+    /// Mark the class as `abstract`, which can't be instantiated, even if it
+    /// has no abstract methods.
+    pub fn set_abstract(&mut self) -> &mut Self {
+        self.flags |= ZEND_ACC_EXPLICIT_ABSTRACT_CLASS;
+        self
+    }
+
+    /// Mark the class as `readonly`, all the properties become readonly and
+    /// the dynamic properties are forbidden.
+    ///
+    /// Notice that the readonly properties must be typed, so the untyped
+    /// properties can't be declared in the readonly class.
+    #[cfg(all(
+        phper_major_version = "8",
+        not(any(phper_minor_version = "0", phper_minor_version = "1"))
+    ))]
+    pub fn set_readonly(&mut self) -> &mut Self {
+        self.flags |= ZEND_ACC_READONLY_CLASS | ZEND_ACC_NO_DYNAMIC_PROPERTIES;
+        self
+    }
+
+    /// Add attribute to the class.
+    #[cfg(phper_major_version = "8")]
+    pub fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Declare the class as an attribute class, just like marking the class
+    /// with `#[Attribute]`, so it can be instantiated by
+    /// `ReflectionAttribute::newInstance()`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{attributes::AttributeTarget, classes::ClassEntity};
+    ///
+    /// let mut class = ClassEntity::new("Route");
+    /// class.set_attribute_class([AttributeTarget::Class, AttributeTarget::Method], false);
+    /// ```
+    #[cfg(phper_major_version = "8")]
+    pub fn set_attribute_class(
+        &mut self, targets: impl IntoIterator<Item = AttributeTarget>, repeatable: bool,
+    ) -> &mut Self {
+        let mut flags = targets
+            .into_iter()
+            .fold(0, |flags, target| flags | target as u32);
+        if repeatable {
+            flags |= ZEND_ATTRIBUTE_IS_REPEATABLE;
+        }
+        self.attribute_flags = Some(flags);
+        self
+    }
+
     /// Add the state clone function, called when cloning PHP object.
     ///
     /// By default, the object registered by `phper` is uncloneable, if you
@@ -694,6 +1151,19 @@ impl<T: 'static> ClassEntity<T> {
 
             self.bound_class.bind(class_ce);
 
+            (*class_ce).ce_flags |= self.flags;
+
+            if !self.magic_method_entities.is_empty() {
+                self.hidden_function_table
+                    .set(phper_zend_register_hidden_methods(
+                        class_ce,
+                        self.magic_function_entries(),
+                    ));
+            }
+
+            #[cfg(phper_major_version = "8")]
+            self.register_attributes(class_ce);
+
             for interface in &self.interfaces {
                 let interface_ce = interface.as_class_entry().as_ptr();
                 zend_class_implements(class_ce, 1, interface_ce);
@@ -709,12 +1179,71 @@ impl<T: 'static> ClassEntity<T> {
         }
     }
 
+    #[cfg(phper_major_version = "8")]
+    unsafe fn register_attributes(&self, ce: *mut zend_class_entry) {
+        unsafe {
+            for attribute in &self.attributes {
+                attribute.add_to(&mut (*ce).attributes, 0);
+            }
+            if let Some(flags) = self.attribute_flags {
+                zend_internal_attribute_register(ce, flags);
+            }
+            for method in &self.method_entities {
+                method.register_attributes(ce);
+            }
+        }
+    }
+
+    /// Free the function table of the magic methods, called in `MSHUTDOWN`.
+    pub(crate) unsafe fn shutdown(&self) {
+        let function_table = self.hidden_function_table.replace(null_mut());
+        if !function_table.is_null() {
+            unsafe {
+                phper_zend_destroy_hidden_methods(function_table);
+            }
+        }
+    }
+
     pub(crate) unsafe fn declare_properties(&self, ce: *mut zend_class_entry) {
         for property in &self.property_entities {
             property.declare(ce);
         }
     }
 
+    /// Copy the members of used traits into the class, should be called after
+    /// [ClassEntity::declare_properties], returns the handlers of the copied
+    /// methods.
+    pub(crate) unsafe fn use_traits(
+        &self, ce: *mut zend_class_entry, trait_handler_map: &TraitHandlerMap,
+    ) -> HandlerMap {
+        unsafe {
+            let mut handler_map = HandlerMap::new();
+            for r#trait in &self.traits {
+                let trait_ce = r#trait.as_class_entry().as_ptr() as *mut zend_class_entry;
+                phper_zend_class_use_trait(ce, trait_ce);
+
+                for (_, func) in ZArr::from_ptr(&(*trait_ce).function_table).iter() {
+                    let func = (*func.as_ptr()).value.ptr as *const zend_function;
+                    let arg_info = (*func).internal_function.arg_info.cast_const();
+                    let Some(handler) = trait_handler_map.get(&arg_info) else {
+                        continue;
+                    };
+                    let name = ZStr::from_ptr((*func).common.function_name).to_bytes();
+                    let declared = self
+                        .method_entities
+                        .iter()
+                        .any(|method| method.name.to_bytes().eq_ignore_ascii_case(name));
+                    if !declared {
+                        handler_map
+                            .entry((Some(self.class_name.clone()), CString::new(name).unwrap()))
+                            .or_insert_with(|| handler.clone());
+                    }
+                }
+            }
+            handler_map
+        }
+    }
+
     unsafe fn function_entries(&self) -> *const zend_function_entry {
         unsafe {
             let mut methods = self
@@ -735,6 +1264,20 @@ impl<T: 'static> ClassEntity<T> {
         }
     }
 
+    unsafe fn magic_function_entries(&self) -> *const zend_function_entry {
+        unsafe {
+            let mut methods = self
+                .magic_method_entities
+                .iter()
+                .map(|method| FunctionEntry::from_method_entity(method))
+                .collect::<Vec<_>>();
+
+            methods.push(zeroed::<zend_function_entry>());
+
+            Box::into_raw(methods.into_boxed_slice()).cast()
+        }
+    }
+
     unsafe fn take_state_constructor_into_function_entry(&self) -> zend_function_entry {
         unsafe {
             let mut entry = zeroed::<zend_function_entry>();
@@ -760,6 +1303,7 @@ impl<T: 'static> ClassEntity<T> {
     pub(crate) fn handler_map(&self) -> HandlerMap {
         self.method_entities
             .iter()
+            .chain(&self.magic_method_entities)
             .filter_map(|method| {
                 method.handler.as_ref().map(|handler| {
                     (
@@ -794,6 +1338,21 @@ impl<T: 'static> ClassEntity<T> {
     }
 }
 
+/// The name passed to the magic methods.
+fn magic_name(arguments: &[ZVal]) -> &ZStr {
+    arguments[0]
+        .as_z_str()
+        .expect("the name of magic method should be string")
+}
+
+/// The arguments passed to `__call` and `__callStatic` as array.
+fn magic_arguments(arguments: &[ZVal]) -> Vec<ZVal> {
+    arguments[1]
+        .as_z_arr()
+        .map(|arr| arr.iter().map(|(_, value)| value.clone()).collect())
+        .unwrap_or_default()
+}
+
 unsafe extern "C" fn class_init_handler(
     class_ce: *mut zend_class_entry, argument: *mut c_void,
 ) -> *mut zend_class_entry {
@@ -884,6 +1443,11 @@ impl InterfaceEntity {
 
             self.bound_interface.bind(class_ce);
 
+            #[cfg(phper_major_version = "8")]
+            for method in &self.method_entities {
+                method.register_attributes(class_ce);
+            }
+
             for interface in &self.extends {
                 let interface_ce = interface.as_class_entry().as_ptr();
                 zend_class_implements(class_ce, 1, interface_ce);
@@ -924,6 +1488,216 @@ unsafe extern "C" fn interface_init_handler(
     unsafe { zend_register_internal_interface(class_ce) }
 }
 
+/// Builder for registering trait.
+///
+/// The trait can be used by the userland classes, or by the classes registered
+/// by [ClassEntity::uses]. The methods receive the `$this` object as [ZObj],
+/// because the using class is unknown.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{
+///     classes::{TraitEntity, Visibility},
+///     modules::Module,
+///     php_get_module,
+/// };
+///
+/// #[php_get_module]
+/// pub fn get_module() -> Module {
+///     let mut module = Module::new(
+///         env!("CARGO_CRATE_NAME"),
+///         env!("CARGO_PKG_VERSION"),
+///         env!("CARGO_PKG_AUTHORS"),
+///     );
+///
+///     let mut counter = TraitEntity::new("Counter");
+///     counter.add_property("count", Visibility::Private, 0);
+///     counter.add_method("increase", Visibility::Public, |this, _| {
+///         let count = this.get_property("count").as_long().unwrap_or_default() + 1;
+///         this.set_property("count", count);
+///         phper::ok(count)
+///     });
+///     module.add_trait(counter);
+///
+///     module
+/// }
+/// ```
+pub struct TraitEntity {
+    trait_name: CString,
+    method_entities: Vec<MethodEntity>,
+    property_entities: Vec<PropertyEntity>,
+    bound_trait: Trait,
+}
+
+impl TraitEntity {
+    /// Construct a new `TraitEntity` with trait name.
+    pub fn new(trait_name: impl Into<String>) -> Self {
+        Self {
+            trait_name: ensure_end_with_zero(trait_name.into()),
+            method_entities: Vec::new(),
+            property_entities: Vec::new(),
+            bound_trait: Trait::null(),
+        }
+    }
+
+    /// Add member method to trait, with visibility and method handler.
+    pub fn add_method<F, Z, E>(
+        &mut self, name: impl Into<String>, vis: Visibility, handler: F,
+    ) -> &mut MethodEntity
+    where
+        F: Fn(&mut ZObj, &mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        self.method_entities.push(MethodEntity::new(
+            name,
+            Some(Rc::new(TraitMethod::new(handler))),
+            vis,
+        ));
+        self.method_entities.last_mut().unwrap()
+    }
+
+    /// Add static method to trait, with visibility and method handler.
+    pub fn add_static_method<F, Z, E>(
+        &mut self, name: impl Into<String>, vis: Visibility, handler: F,
+    ) -> &mut MethodEntity
+    where
+        F: Fn(&mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        let mut entity = MethodEntity::new(name, Some(Rc::new(Function::new(handler))), vis);
+        entity.set_vis_static();
+        self.method_entities.push(entity);
+        self.method_entities.last_mut().unwrap()
+    }
+
+    /// Add abstract method to trait, which should be implemented by the using
+    /// class.
+    pub fn add_abstract_method(
+        &mut self, name: impl Into<String>, vis: Visibility,
+    ) -> &mut MethodEntity {
+        let mut entity = MethodEntity::new(name, None, vis);
+        entity.set_vis_abstract();
+        self.method_entities.push(entity);
+        self.method_entities.last_mut().unwrap()
+    }
+
+    /// Declare property, see [ClassEntity::add_property].
+    pub fn add_property(
+        &mut self, name: impl Into<String>, visibility: Visibility, value: impl Into<Scalar>,
+    ) {
+        self.property_entities
+            .push(PropertyEntity::new(name, visibility, value));
+    }
+
+    /// Declare static property, see [ClassEntity::add_static_property].
+    pub fn add_static_property(
+        &mut self, name: impl Into<String>, visibility: Visibility, value: impl Into<Scalar>,
+    ) {
+        let mut entity = PropertyEntity::new(name, visibility, value);
+        entity.set_vis_static();
+        self.property_entities.push(entity);
+    }
+
+    #[allow(clippy::useless_conversion)]
+    pub(crate) unsafe fn init(&self) -> *mut zend_class_entry {
+        unsafe {
+            let class_ce = phper_init_class_entry_ex(
+                self.trait_name.as_ptr().cast(),
+                self.trait_name.as_bytes().len().try_into().unwrap(),
+                self.function_entries(),
+                Some(trait_init_handler),
+                null_mut(),
+            );
+
+            self.bound_trait.bind(class_ce);
+
+            #[cfg(phper_major_version = "8")]
+            for method in &self.method_entities {
+                method.register_attributes(class_ce);
+            }
+
+            for property in &self.property_entities {
+                property.declare(class_ce);
+            }
+
+            class_ce
+        }
+    }
+
+    unsafe fn function_entries(&self) -> *const zend_function_entry {
+        unsafe {
+            let mut methods = self
+                .method_entities
+                .iter()
+                .map(|method| FunctionEntry::from_method_entity(method))
+                .collect::<Vec<_>>();
+
+            methods.push(zeroed::<zend_function_entry>());
+
+            Box::into_raw(methods.into_boxed_slice()).cast()
+        }
+    }
+
+    pub(crate) fn handler_map(&self) -> HandlerMap {
+        self.method_entities
+            .iter()
+            .filter_map(|method| {
+                method.handler.as_ref().map(|handler| {
+                    (
+                        (Some(self.trait_name.clone()), method.name.clone()),
+                        handler.clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Find the handlers by the arg info of the registered methods, which is
+    /// kept by the copies in the using classes.
+    #[allow(clippy::useless_conversion)]
+    pub(crate) unsafe fn trait_handler_map(&self, ce: *mut zend_class_entry) -> TraitHandlerMap {
+        unsafe {
+            self.method_entities
+                .iter()
+                .filter_map(|method| {
+                    let handler = method.handler.as_ref()?;
+                    let name = method.name.to_bytes().to_ascii_lowercase();
+                    let func: *const zend_function = phper_zend_hash_str_find_ptr(
+                        &(*ce).function_table,
+                        name.as_ptr().cast(),
+                        name.len().try_into().unwrap(),
+                    )
+                    .cast();
+                    let func = func.as_ref()?;
+                    Some((
+                        func.internal_function.arg_info.cast_const(),
+                        handler.clone(),
+                    ))
+                })
+                .collect()
+        }
+    }
+
+    /// Get the bound trait.
+    #[inline]
+    pub fn bound_trait(&self) -> Trait {
+        self.bound_trait.clone()
+    }
+}
+
+unsafe extern "C" fn trait_init_handler(
+    class_ce: *mut zend_class_entry, _argument: *mut c_void,
+) -> *mut zend_class_entry {
+    unsafe {
+        let class_ce = zend_register_internal_class(class_ce);
+        (*class_ce).ce_flags |= ZEND_ACC_TRAIT;
+        class_ce
+    }
+}
+
 /// Builder for registering class/interface constants
 pub struct ConstantEntity {
     name: String,
@@ -940,10 +1714,23 @@ impl ConstantEntity {
 }
 
 /// Builder for declare class property.
-pub(crate) struct PropertyEntity {
+pub struct PropertyEntity {
     name: String,
     visibility: RawVisibility,
-    value: Scalar,
+    value: Option<Scalar>,
+    type_hint: Option<ArgumentTypeHint>,
+    #[cfg(not(all(
+        phper_major_version = "7",
+        any(
+            phper_minor_version = "0",
+            phper_minor_version = "1",
+            phper_minor_version = "2",
+            phper_minor_version = "3"
+        )
+    )))]
+    allow_null: bool,
+    #[cfg(phper_major_version = "8")]
+    attributes: Vec<Attribute>,
 }
 
 impl PropertyEntity {
@@ -953,7 +1740,48 @@ impl PropertyEntity {
         Self {
             name: name.into(),
             visibility: visibility as RawVisibility,
-            value: value.into(),
+            value: Some(value.into()),
+            type_hint: None,
+            #[cfg(not(all(
+                phper_major_version = "7",
+                any(
+                    phper_minor_version = "0",
+                    phper_minor_version = "1",
+                    phper_minor_version = "2",
+                    phper_minor_version = "3"
+                )
+            )))]
+            allow_null: false,
+            #[cfg(phper_major_version = "8")]
+            attributes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn new_typed(
+        name: impl Into<String>, visibility: Visibility, type_hint: ArgumentTypeHint,
+    ) -> Self {
+        let name = name.into();
+        assert!(
+            !matches!(type_hint, ArgumentTypeHint::Callable),
+            "property `{name}` can't be typed as callable"
+        );
+        Self {
+            name,
+            visibility: visibility as RawVisibility,
+            value: None,
+            type_hint: Some(type_hint),
+            #[cfg(not(all(
+                phper_major_version = "7",
+                any(
+                    phper_minor_version = "0",
+                    phper_minor_version = "1",
+                    phper_minor_version = "2",
+                    phper_minor_version = "3"
+                )
+            )))]
+            allow_null: false,
+            #[cfg(phper_major_version = "8")]
+            attributes: Vec::new(),
         }
     }
 
@@ -963,14 +1791,171 @@ impl PropertyEntity {
         self
     }
 
-    #[allow(clippy::useless_conversion)]
+    /// Set the default value, otherwise the typed property is uninitialized
+    /// until it's assigned.
+    ///
+    /// The value should match the type, it isn't checked when declaring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is readonly, which can't have default value.
+    #[inline]
+    pub fn default_value(&mut self, value: impl Into<Scalar>) -> &mut Self {
+        #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+        assert!(
+            self.visibility & ZEND_ACC_READONLY == 0,
+            "readonly property `{}` can't have default value",
+            self.name
+        );
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Allow the type to be nullable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is untyped, or typed as `mixed` or `null`, which
+    /// are already nullable.
+    #[inline]
+    pub fn allow_null(&mut self) -> &mut Self {
+        match &self.type_hint {
+            None => panic!("untyped property `{}` can't be nullable", self.name),
+            Some(ArgumentTypeHint::Mixed | ArgumentTypeHint::Null) => panic!(
+                "property `{}` typed as mixed or null can't be nullable",
+                self.name
+            ),
+            Some(_) => {}
+        }
+        #[cfg(not(all(
+            phper_major_version = "7",
+            any(
+                phper_minor_version = "0",
+                phper_minor_version = "1",
+                phper_minor_version = "2",
+                phper_minor_version = "3"
+            )
+        )))]
+        {
+            self.allow_null = true;
+        }
+        self
+    }
+
+    /// Mark the property as `readonly`, which can only be initialized once
+    /// from inside the class scope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is untyped, static or has default value.
+    #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+    #[inline]
+    pub fn set_readonly(&mut self) -> &mut Self {
+        assert!(
+            self.type_hint.is_some(),
+            "readonly property `{}` must be typed",
+            self.name
+        );
+        assert!(
+            self.visibility & ZEND_ACC_STATIC == 0,
+            "static property `{}` can't be readonly",
+            self.name
+        );
+        assert!(
+            self.value.is_none(),
+            "readonly property `{}` can't have default value",
+            self.name
+        );
+        self.visibility |= ZEND_ACC_READONLY;
+        self
+    }
+
+    /// Add attribute to the property.
+    #[cfg(phper_major_version = "8")]
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
     pub(crate) fn declare(&self, ce: *mut zend_class_entry) {
+        match &self.type_hint {
+            #[cfg(not(all(
+                phper_major_version = "7",
+                any(
+                    phper_minor_version = "0",
+                    phper_minor_version = "1",
+                    phper_minor_version = "2",
+                    phper_minor_version = "3"
+                )
+            )))]
+            Some(type_hint) => self.declare_typed(ce, type_hint),
+            // Typed properties are supported since PHP 7.4.
+            _ => self.declare_untyped(ce),
+        }
+
+        #[cfg(phper_major_version = "8")]
+        unsafe {
+            let info: *mut zend_property_info = phper_zend_hash_str_find_ptr(
+                &(*ce).properties_info,
+                self.name.as_ptr().cast(),
+                self.name.len(),
+            )
+            .cast();
+            if let Some(info) = info.as_mut() {
+                for attribute in &self.attributes {
+                    attribute.add_to(&mut info.attributes, 0);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(all(
+        phper_major_version = "7",
+        any(
+            phper_minor_version = "0",
+            phper_minor_version = "1",
+            phper_minor_version = "2",
+            phper_minor_version = "3"
+        )
+    )))]
+    fn declare_typed(&self, ce: *mut zend_class_entry, type_hint: &ArgumentTypeHint) {
+        let (type_code, class_name) = match type_hint {
+            ArgumentTypeHint::ClassEntry(class_name) => {
+                (0, Some(ensure_end_with_zero(class_name.as_str())))
+            }
+            type_hint => (type_hint.zend_type_const().unwrap_or(IS_MIXED), None),
+        };
+
+        unsafe {
+            let mut value = match &self.value {
+                Some(value) => value.to_persistent_zval(),
+                None => zeroed::<zval>(),
+            };
+            phper_zend_declare_typed_property(
+                ce,
+                self.name.as_ptr().cast(),
+                self.name.len(),
+                &mut value,
+                self.visibility as i32,
+                type_code,
+                class_name
+                    .as_ref()
+                    .map(|name| name.as_ptr())
+                    .unwrap_or(null()),
+                self.allow_null,
+            );
+        }
+    }
+
+    #[allow(clippy::useless_conversion)]
+    fn declare_untyped(&self, ce: *mut zend_class_entry) {
         let name = self.name.as_ptr().cast();
         let name_length = self.name.len().try_into().unwrap();
         let access_type = self.visibility as i32;
 
         unsafe {
-            match &self.value {
+            match self.value.as_ref().unwrap_or(&Scalar::Null) {
                 Scalar::Null => {
                     zend_declare_property_null(ce, name, name_length, access_type);
                 }
@@ -1005,6 +1990,10 @@ impl PropertyEntity {
                         access_type,
                     );
                 }
+                value @ Scalar::Array(_) => {
+                    let mut value = value.to_persistent_zval();
+                    zend_declare_property(ce, name, name_length, &mut value, access_type);
+                }
             }
         }
     }
@@ -1028,6 +2017,18 @@ pub enum Visibility {
 /// Raw visibility flag.
 pub(crate) type RawVisibility = u32;
 
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+#[inline]
+fn is_enum_flags(flags: u32) -> bool {
+    flags & ZEND_ACC_ENUM != 0
+}
+
+#[cfg(not(all(phper_major_version = "8", not(phper_minor_version = "0"))))]
+#[inline]
+fn is_enum_flags(_flags: u32) -> bool {
+    false
+}
+
 #[allow(clippy::useless_conversion)]
 pub(crate) unsafe extern "C" fn create_object(ce: *mut zend_class_entry) -> *mut zend_object {
     unsafe {
@@ -1187,6 +2188,10 @@ pub(crate) unsafe fn add_class_constant(
                     s.len(),
                 );
             }
+            Scalar::Array(_) => {
+                let mut value = constant.value.to_persistent_zval();
+                zend_declare_class_constant(class_ce, name_ptr, name_len, &mut value);
+            }
         }
     }
 }
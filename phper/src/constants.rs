@@ -10,26 +10,77 @@
 
 //! Apis relate to [zend_constant](crate::sys::zend_constant).
 
-use crate::{sys::*, types::Scalar};
-use std::ffi::{c_char, c_int};
+use crate::{strings::ZString, sys::*, types::Scalar, values::ZVal};
+use std::{
+    ffi::{c_char, c_int},
+    ptr::null_mut,
+};
 
-pub(crate) struct Constant {
+/// Get the constant value by name, like `PHP_VERSION`, `E_ALL` or the
+/// constants defined by `define()`.
+///
+/// The class constant can be fetched by `Foo::BAR` too, returns `None` if the
+/// constant isn't defined or isn't accessible.
+pub fn get(name: &str) -> Option<ZVal> {
+    get_with_scope(name.as_bytes(), null_mut())
+}
+
+pub(crate) fn get_with_scope(name: &[u8], scope: *mut zend_class_entry) -> Option<ZVal> {
+    let mut name = ZString::new(name);
+    unsafe {
+        let value = zend_get_constant_ex(name.as_mut_ptr(), scope, ZEND_FETCH_CLASS_SILENT);
+        ZVal::try_from_ptr(value).cloned()
+    }
+}
+
+/// Constant registered by
+/// [Module::add_constant](crate::modules::Module::add_constant).
+pub struct Constant {
     name: String,
     value: Scalar,
+    flags: u32,
+    #[cfg(phper_php_8_5_or_later)]
+    attributes: Vec<String>,
 }
 
 impl Constant {
-    pub fn new(name: impl Into<String>, value: impl Into<Scalar>) -> Self {
+    pub(crate) fn new(name: impl Into<String>, value: impl Into<Scalar>) -> Self {
         Self {
             name: name.into(),
             value: value.into(),
+            flags: CONST_PERSISTENT | CONST_CS,
+            #[cfg(phper_php_8_5_or_later)]
+            attributes: Vec::new(),
         }
     }
 
+    /// Register the constant as case-insensitive, only supported before PHP
+    /// 8.0.
+    #[cfg(phper_major_version = "7")]
+    pub fn case_insensitive(&mut self) -> &mut Self {
+        self.flags &= !CONST_CS;
+        self
+    }
+
+    /// Mark the constant as deprecated, accessing it will emit `E_DEPRECATED`.
+    #[cfg(phper_major_version = "8")]
+    pub fn deprecated(&mut self) -> &mut Self {
+        self.flags |= CONST_DEPRECATED;
+        self
+    }
+
+    /// Add the attribute without arguments to the constant, which can be read
+    /// by `ReflectionConstant::getAttributes()`.
+    #[cfg(phper_php_8_5_or_later)]
+    pub fn add_attribute(&mut self, name: impl Into<String>) -> &mut Self {
+        self.attributes.push(name.into());
+        self
+    }
+
     pub(crate) fn register(&self, module_number: c_int) {
         let name_ptr = self.name.as_ptr() as *const c_char;
         let name_len = self.name.len();
-        let flags = (CONST_PERSISTENT | CONST_CS) as c_int;
+        let flags = self.flags as c_int;
 
         unsafe {
             match &self.value {
@@ -75,7 +126,27 @@ impl Constant {
                         module_number,
                     )
                 }
+                Scalar::Array(_) => {
+                    let mut value = self.value.to_persistent_zval();
+                    phper_zend_register_zval_constant(
+                        name_ptr,
+                        name_len,
+                        &mut value,
+                        flags,
+                        module_number,
+                    )
+                }
             };
+
+            #[cfg(phper_php_8_5_or_later)]
+            for attribute in &self.attributes {
+                phper_zend_constant_add_attribute(
+                    name_ptr,
+                    name_len,
+                    attribute.as_ptr().cast(),
+                    attribute.len(),
+                );
+            }
         }
     }
 }
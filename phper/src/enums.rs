@@ -20,13 +20,14 @@
 #![cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
 
 use crate::{
+    alloc::ToRefOwned,
     classes::{
         ClassEntry, ConstantEntity, InnerClassEntry, Interface, Visibility, add_class_constant,
     },
-    errors::Throwable,
-    functions::{Function, FunctionEntry, HandlerMap, MethodEntity},
+    errors::{EnumCaseNotFoundError, ExpectClassError, Throwable},
+    functions::{Function, FunctionEntry, HandlerMap, MethodEntity, TraitMethod},
     objects::ZObj,
-    strings::ZString,
+    strings::{ZStr, ZString},
     sys::*,
     types::Scalar,
     utils::ensure_end_with_zero,
@@ -126,22 +127,20 @@ impl EnumCase {
 
     /// Gets a reference to the enum case.
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// A reference to ZObj representing the enum case, or an error if the case
-    /// doesn't exist
+    /// Panics if the enum isn't registered.
     pub fn get_case<'a>(&self) -> &'a ZObj {
-        unsafe { self.r#enum.get_case(&self.case_name).unwrap() }
+        self.r#enum.get_case(&self.case_name).unwrap()
     }
 
     /// Gets a mutable reference to the enum case.
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// A mutable reference to ZObj representing the enum case, or an error if
-    /// the case doesn't exist
+    /// Panics if the enum isn't registered.
     pub fn get_mut_case<'a>(&mut self) -> &'a mut ZObj {
-        unsafe { self.r#enum.get_mut_case(&self.case_name).unwrap() }
+        self.r#enum.get_mut_case(&self.case_name).unwrap()
     }
 
     /// Gets the name of the enum case.
@@ -246,28 +245,9 @@ impl Enum {
     ///
     /// A reference to ZObj representing the enum case, or an error if the case
     /// doesn't exist
-    ///
-    /// # Safety
-    ///
-    /// This function is marked as unsafe because the underlying
-    /// `zend_enum_get_case` function may cause a SIGSEGV (segmentation
-    /// fault) when the case doesn't exist. Even though this method attempts
-    /// to check for null and return an error instead, there might still be
-    /// scenarios where the PHP internal function behaves unpredictably.
-    /// Callers must ensure the enum and case name are valid before calling this
-    /// function.
-    pub unsafe fn get_case<'a>(&self, case_name: impl AsRef<str>) -> crate::Result<&'a ZObj> {
-        unsafe {
-            let ce = self.as_class_entry().as_ptr() as *mut _;
-            let case_name_str = case_name.as_ref();
-            let mut name_zstr = ZString::new(case_name_str);
-
-            // Get the enum case
-            let case_obj = zend_enum_get_case(ce, name_zstr.as_mut_ptr());
-
-            // Convert to &ZObj
-            Ok(ZObj::from_ptr(case_obj))
-        }
+    pub fn get_case<'a>(&self, case_name: impl AsRef<str>) -> crate::Result<&'a ZObj> {
+        self.find_case(case_name.as_ref())
+            .map(|case_obj| unsafe { ZObj::from_ptr(case_obj) })
     }
 
     /// Get a mutable reference to an enum case by name.
@@ -280,31 +260,202 @@ impl Enum {
     ///
     /// A mutable reference to ZObj representing the enum case, or an error if
     /// the case doesn't exist
+    pub fn get_mut_case<'a>(&mut self, case_name: impl AsRef<str>) -> crate::Result<&'a mut ZObj> {
+        self.find_case(case_name.as_ref())
+            .map(|case_obj| unsafe { ZObj::from_mut_ptr(case_obj) })
+    }
+
+    /// Get an enum case by the backing value, like `Enum::tryFrom()` in PHP.
+    ///
+    /// Return an error if the enum is pure, or no case has the value.
+    ///
+    /// # Examples
     ///
-    /// # Safety
-    ///
-    /// This function is marked as unsafe because the underlying
-    /// `zend_enum_get_case` function may cause a SIGSEGV (segmentation
-    /// fault) when the case doesn't exist. Even though this method attempts
-    /// to check for null and return an error instead, there might still be
-    /// scenarios where the PHP internal function behaves unpredictably.
-    /// Callers must ensure the enum and case name are valid before calling this
-    /// function.
-    pub unsafe fn get_mut_case<'a>(
-        &mut self, case_name: impl AsRef<str>,
-    ) -> crate::Result<&'a mut ZObj> {
+    /// ```no_run
+    /// use phper::enums::Enum;
+    ///
+    /// let suit = Enum::from_name("Suit");
+    /// let hearts = suit.try_from("H")?;
+    /// # Ok::<_, phper::Error>(())
+    /// ```
+    pub fn try_from<'a>(&self, value: impl Into<ZVal>) -> crate::Result<&'a ZObj> {
+        let value = value.into();
         unsafe {
             let ce = self.as_class_entry().as_ptr() as *mut _;
-            let case_name_str = case_name.as_ref();
-            let mut name_zstr = ZString::new(case_name_str);
+            let case_obj = phper_zend_enum_find_case_by_value(ce, value.as_ptr());
+            ZObj::try_from_ptr(case_obj)
+                .ok_or_else(|| self.case_not_found(backing_value_repr(&value)))
+        }
+    }
+
+    /// Get all the cases in declaration order, like `Enum::cases()` in PHP.
+    pub fn cases<'a>(&self) -> Vec<&'a ZObj> {
+        let mut cases = ZVal::default();
+        unsafe {
+            let ce = self.as_class_entry().as_ptr() as *mut _;
+            phper_zend_enum_cases(ce, cases.as_mut_ptr());
+        }
+        let cases = cases.as_z_arr().unwrap();
+        // The case objects are held by the class constants, so they outlive
+        // the array.
+        cases
+            .iter()
+            .filter_map(|(_, case_obj)| case_obj.as_z_obj())
+            .map(|case_obj| unsafe { ZObj::from_ptr(case_obj.as_ptr()) })
+            .collect()
+    }
+
+    fn find_case(&self, case_name: &str) -> crate::Result<*mut zend_object> {
+        unsafe {
+            let ce = self.as_class_entry().as_ptr() as *mut _;
+            let case_obj =
+                phper_zend_enum_find_case(ce, case_name.as_ptr().cast(), case_name.len());
+            if case_obj.is_null() {
+                Err(self.case_not_found(case_name.to_owned()))
+            } else {
+                Ok(case_obj)
+            }
+        }
+    }
+
+    fn case_not_found(&self, case: String) -> crate::Error {
+        let enum_name = self.as_class_entry().get_name().to_string_lossy();
+        EnumCaseNotFoundError::new(enum_name.into_owned(), case).into()
+    }
+}
+
+/// Format the backing value like PHP does in the error message.
+fn backing_value_repr(value: &ZVal) -> String {
+    if let Some(l) = value.as_long() {
+        l.to_string()
+    } else if let Some(s) = value.as_z_str() {
+        format!("\"{}\"", s.to_string_lossy())
+    } else {
+        value
+            .get_type_info()
+            .get_base_type_name()
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Wrapper of the enum case object, passed to the methods added by
+/// [EnumEntity::add_method].
+#[repr(transparent)]
+pub struct EnumCaseObj {
+    inner: ZObj,
+}
+
+impl EnumCaseObj {
+    /// Downcast from the object, return `None` if the object isn't an enum
+    /// case.
+    pub fn from_z_obj(obj: &ZObj) -> Option<&Self> {
+        unsafe {
+            if (*obj.get_class().as_ptr()).ce_flags & ZEND_ACC_ENUM == 0 {
+                return None;
+            }
+            Some(&*(obj as *const ZObj as *const Self))
+        }
+    }
 
-            // Get the enum case
-            let case_obj = zend_enum_get_case(ce, name_zstr.as_mut_ptr());
+    /// Gets the name of the case.
+    pub fn name(&self) -> &ZStr {
+        unsafe {
+            ZStr::from_ptr(phper_zend_enum_fetch_case_name(
+                self.inner.as_ptr() as *mut _
+            ))
+        }
+    }
 
-            // Convert to &mut ZObj
-            Ok(ZObj::from_mut_ptr(case_obj as *mut _))
+    /// Gets the backing value of the case, return `None` if the enum is pure.
+    pub fn value(&self) -> Option<&ZVal> {
+        unsafe {
+            let value = phper_zend_enum_fetch_case_value(self.inner.as_ptr() as *mut _);
+            value.as_ref().map(|value| ZVal::from_ptr(value))
         }
     }
+
+    /// Gets the enum case object.
+    #[inline]
+    pub fn as_z_obj(&self) -> &ZObj {
+        &self.inner
+    }
+}
+
+/// Rust fieldless enum mapped to a PHP enum, usually implemented by
+/// [`#[derive(PhpEnum)]`](crate::PhpEnum).
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{PhpEnum, enums::PhpEnum as _, modules::Module, values::ZVal};
+///
+/// #[derive(PhpEnum)]
+/// #[php_enum(name = "App\\Suit")]
+/// enum Suit {
+///     #[php_enum(value = "H")]
+///     Hearts,
+///     #[php_enum(value = "S")]
+///     Spades,
+/// }
+///
+/// let mut module = Module::new("app", "0.1.0", "");
+/// module.add_enum(Suit::enum_entity());
+/// module.add_function("lucky_suit", |_| phper::ok(ZVal::from(Suit::Hearts)));
+/// ```
+pub trait PhpEnum: Sized + 'static {
+    /// The backing type, `()` for pure enum.
+    type Backing: EnumBackingType;
+
+    /// The name of the PHP enum.
+    const ENUM_NAME: &'static str;
+
+    /// All the variants in declaration order.
+    const VARIANTS: &'static [Self];
+
+    /// Gets the PHP case name of the variant.
+    fn case_name(&self) -> &'static str;
+
+    /// Gets the backing value of the variant.
+    fn case_value(&self) -> Self::Backing;
+
+    /// Gets the variant by the PHP case name.
+    fn from_case_name(name: &str) -> Option<Self>;
+
+    /// Create the [EnumEntity] with all the cases, the methods, constants and
+    /// interfaces can be added before registering.
+    fn enum_entity() -> EnumEntity<Self::Backing> {
+        let mut entity = EnumEntity::new(Self::ENUM_NAME);
+        for variant in Self::VARIANTS {
+            entity.add_case(variant.case_name(), variant.case_value());
+        }
+        entity
+    }
+
+    /// Convert to the PHP enum case value.
+    fn to_zval(&self) -> crate::Result<ZVal> {
+        let case_obj = Enum::from_name(Self::ENUM_NAME).get_case(self.case_name())?;
+        Ok(case_obj.to_ref_owned().into())
+    }
+
+    /// Convert from the PHP enum case value, returns the type error if the
+    /// value isn't the case of the enum.
+    fn from_zval(value: &ZVal) -> crate::Result<Self> {
+        let obj = value.expect_z_obj()?;
+        let class = ClassEntry::from_globals(Self::ENUM_NAME)?;
+        if !obj.get_class().is_instance_of(class) {
+            return Err(ExpectClassError::new(
+                Self::ENUM_NAME.to_owned(),
+                obj.get_class().get_name().to_string_lossy().into_owned(),
+            )
+            .into());
+        }
+        let case_name = EnumCaseObj::from_z_obj(obj)
+            .map(|case_obj| case_obj.name().to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::from_case_name(&case_name)
+            .ok_or_else(|| EnumCaseNotFoundError::new(Self::ENUM_NAME.to_owned(), case_name).into())
+    }
 }
 
 /// Builder for registering a PHP enum.
@@ -373,6 +524,43 @@ impl<B: EnumBackingType> EnumEntity<B> {
         EnumCase::new(self.bound_enum(), case_name_str)
     }
 
+    /// Adds a method to the enum, the handler can access the case name and
+    /// backing value by [EnumCaseObj].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{classes::Visibility, enums::EnumEntity};
+    ///
+    /// let mut suit = EnumEntity::<String>::new("Suit");
+    /// suit.add_case("Hearts", "H".to_owned());
+    /// suit.add_method("label", Visibility::Public, |this, _| {
+    ///     phper::ok(format!(
+    ///         "{} ({})",
+    ///         this.name().to_str()?,
+    ///         this.value().unwrap().expect_z_str()?.to_str()?
+    ///     ))
+    /// });
+    /// ```
+    pub fn add_method<F, Z, E>(
+        &mut self, name: impl Into<String>, vis: Visibility, handler: F,
+    ) -> &mut MethodEntity
+    where
+        F: Fn(&EnumCaseObj, &mut [ZVal]) -> Result<Z, E> + 'static,
+        Z: Into<ZVal> + 'static,
+        E: Throwable + 'static,
+    {
+        let handler = TraitMethod::new(move |this: &mut ZObj, arguments: &mut [ZVal]| {
+            handler(
+                EnumCaseObj::from_z_obj(this).expect("`this` should be enum case"),
+                arguments,
+            )
+        });
+        self.method_entities
+            .push(MethodEntity::new(name, Some(Rc::new(handler)), vis));
+        self.method_entities.last_mut().unwrap()
+    }
+
     /// Adds a static method to the enum.
     ///
     /// # Parameters
@@ -433,7 +621,7 @@ impl<B: EnumBackingType> EnumEntity<B> {
     ///     enum_entity.add_case("Inactive", ());
     ///     let mut status_enum = enum_entity.bound_enum();
     ///     enum_entity.add_static_method("getActiveCase", Visibility::Public, move |_| {
-    ///         let active_case = unsafe { status_enum.clone().get_mut_case("Active")? };
+    ///         let active_case = status_enum.clone().get_mut_case("Active")?;
     ///         phper::ok(active_case.to_ref_owned())
     ///     });
     ///     enum_entity
@@ -489,6 +677,10 @@ impl<B: EnumBackingType> EnumEntity<B> {
 
             self.bound_enum.bind(class_ce);
 
+            for method in &self.method_entities {
+                method.register_attributes(class_ce);
+            }
+
             for interface in &self.interfaces {
                 let interface_ce = interface.as_class_entry().as_ptr();
                 zend_class_implements(class_ce, 1, interface_ce);
@@ -45,7 +45,12 @@ macro_rules! throwable_delegate {
             Self::ArgumentCount(e) => Throwable::$method(e),
             Self::InitializeObject(e) => Throwable::$method(e),
             Self::ExpectType(e) => Throwable::$method(e),
+            Self::ExpectClass(e) => Throwable::$method(e),
             Self::NotImplementThrowable(e) => Throwable::$method(e),
+            Self::InvalidIniValue(e) => Throwable::$method(e),
+            Self::SapiHeader(e) => Throwable::$method(e),
+            Self::EnumCaseNotFound(e) => Throwable::$method(e),
+            Self::Interrupted(e) => Throwable::$method(e),
         }
     };
     // For `&mut self` methods (to_object).
@@ -60,7 +65,12 @@ macro_rules! throwable_delegate {
             Self::ArgumentCount(e) => Throwable::$method(e),
             Self::InitializeObject(e) => Throwable::$method(e),
             Self::ExpectType(e) => Throwable::$method(e),
+            Self::ExpectClass(e) => Throwable::$method(e),
             Self::NotImplementThrowable(e) => Throwable::$method(e),
+            Self::InvalidIniValue(e) => Throwable::$method(e),
+            Self::SapiHeader(e) => Throwable::$method(e),
+            Self::EnumCaseNotFound(e) => Throwable::$method(e),
+            Self::Interrupted(e) => Throwable::$method(e),
         }
     };
 }
@@ -102,6 +112,13 @@ pub fn argument_count_error_class<'a>() -> &'a ClassEntry {
     unsafe { ClassEntry::from_ptr(zend_ce_argument_count_error) }
 }
 
+/// Predefined class `ValueError` (>= PHP 8.0.0).
+#[cfg(phper_major_version = "8")]
+#[inline]
+pub fn value_error_class<'a>() -> &'a ClassEntry {
+    unsafe { ClassEntry::from_ptr(zend_ce_value_error) }
+}
+
 /// Predefined class `ArithmeticError`.
 #[inline]
 pub fn arithmetic_error_class<'a>() -> &'a ClassEntry {
@@ -244,9 +261,29 @@ pub enum Error {
     #[error(transparent)]
     ExpectType(#[from] ExpectTypeError),
 
+    /// Expect class is not the class of the actual object.
+    #[error(transparent)]
+    ExpectClass(#[from] ExpectClassError),
+
     /// Failed when the object isn't implement PHP `Throwable`.
     #[error(transparent)]
     NotImplementThrowable(#[from] NotImplementThrowableError),
+
+    /// The ini value can't be parsed.
+    #[error(transparent)]
+    InvalidIniValue(#[from] InvalidIniValueError),
+
+    /// Failed to modify the response header.
+    #[error(transparent)]
+    SapiHeader(#[from] SapiHeaderError),
+
+    /// Enum case not found, get the case by name or backing value failed.
+    #[error(transparent)]
+    EnumCaseNotFound(#[from] EnumCaseNotFoundError),
+
+    /// The execution is interrupted by PHP because of the timeout.
+    #[error(transparent)]
+    Interrupted(#[from] InterruptedError),
 }
 
 impl Error {
@@ -397,6 +434,21 @@ impl Throwable for ExpectTypeError {
     }
 }
 
+/// Expect class is not the class of the actual object.
+#[derive(Debug, thiserror::Error, Constructor)]
+#[error("type error: must be of type {expect_class}, {actual_class} given")]
+pub struct ExpectClassError {
+    expect_class: String,
+    actual_class: String,
+}
+
+impl Throwable for ExpectClassError {
+    #[inline]
+    fn get_class(&self) -> &ClassEntry {
+        type_error_class()
+    }
+}
+
 /// Class not found, get the class by name failed, etc.
 #[derive(Debug, thiserror::Error, Constructor)]
 #[error("Class '{class_name}' not found")]
@@ -435,12 +487,26 @@ impl Throwable for ArgumentCountError {
 }
 
 /// Failed to initialize object.
-#[derive(Debug, thiserror::Error, Constructor)]
-#[error("Cannot instantiate class {class_name}")]
+#[derive(Debug, thiserror::Error)]
+#[error("Cannot instantiate {kind} {class_name}")]
 pub struct InitializeObjectError {
+    kind: &'static str,
     class_name: String,
 }
 
+impl InitializeObjectError {
+    /// Construct a new `InitializeObjectError` for the class.
+    pub fn new(class_name: String) -> Self {
+        Self::with_kind("class", class_name)
+    }
+
+    /// Construct a new `InitializeObjectError` with the kind of class, such as
+    /// `abstract class`, `interface`, `trait` and `enum`.
+    pub fn with_kind(kind: &'static str, class_name: String) -> Self {
+        Self { kind, class_name }
+    }
+}
+
 impl Throwable for InitializeObjectError {
     fn get_class(&self) -> &ClassEntry {
         error_class()
@@ -458,6 +524,67 @@ impl Throwable for NotImplementThrowableError {
     }
 }
 
+/// The ini value can't be parsed.
+#[derive(Debug, thiserror::Error, Constructor)]
+#[error("Invalid ini value \"{value}\"")]
+pub struct InvalidIniValueError {
+    value: String,
+}
+
+impl Throwable for InvalidIniValueError {
+    fn get_class(&self) -> &ClassEntry {
+        error_class()
+    }
+}
+
+/// Failed to modify the response header, because the headers are already sent
+/// or the header is invalid.
+#[derive(Debug, thiserror::Error, Constructor)]
+#[error("Cannot modify header \"{header}\"")]
+pub struct SapiHeaderError {
+    header: String,
+}
+
+impl Throwable for SapiHeaderError {
+    fn get_class(&self) -> &ClassEntry {
+        error_class()
+    }
+}
+
+/// Enum case not found, get the case by name or backing value failed.
+#[derive(Debug, thiserror::Error, Constructor)]
+#[error("{case} is not a valid case of enum {enum_name}")]
+pub struct EnumCaseNotFoundError {
+    enum_name: String,
+    case: String,
+}
+
+impl Throwable for EnumCaseNotFoundError {
+    fn get_class(&self) -> &ClassEntry {
+        #[cfg(phper_major_version = "8")]
+        {
+            value_error_class()
+        }
+
+        #[cfg(not(phper_major_version = "8"))]
+        {
+            error_class()
+        }
+    }
+}
+
+/// The execution is interrupted by PHP while running the Rust code, because
+/// the execution time is exceeded, see [crate::interrupts::check].
+#[derive(Debug, thiserror::Error)]
+#[error("Maximum execution time exceeded")]
+pub struct InterruptedError;
+
+impl Throwable for InterruptedError {
+    fn get_class(&self) -> &ClassEntry {
+        error_class()
+    }
+}
+
 /// Guarder for preventing the thrown exception from being overwritten.
 ///
 /// Normally, you don't need to use `ExceptionGuard`, unless before you call the
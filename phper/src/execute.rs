@@ -0,0 +1,254 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to [zend_execute_ex] and [zend_execute_internal].
+//!
+//! The hooks are registered by
+//! [Module::on_execute_ex](crate::modules::Module::on_execute_ex) and
+//! [Module::on_execute_internal](crate::modules::Module::on_execute_internal),
+//! installed in `MINIT` and the original pointers are restored in
+//! `MSHUTDOWN`.
+
+use crate::{
+    modules::global_module,
+    sys::*,
+    values::{ExecuteData, ZVal},
+};
+use std::{cell::Cell, mem::replace};
+
+type RawExecuteEx = unsafe extern "C" fn(*mut zend_execute_data);
+
+type RawExecuteInternal = unsafe extern "C" fn(*mut zend_execute_data, *mut zval);
+
+#[cfg(phper_zts)]
+pub(crate) type ExecuteExHook = dyn Fn(ExecuteEx<'_>) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+pub(crate) type ExecuteExHook = dyn Fn(ExecuteEx<'_>);
+
+#[cfg(phper_zts)]
+pub(crate) type ExecuteInternalHook = dyn Fn(ExecuteInternal<'_>) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+pub(crate) type ExecuteInternalHook = dyn Fn(ExecuteInternal<'_>);
+
+thread_local! {
+    /// Whether the hook body is running, used to prevent the hook from being
+    /// re-entered by the PHP code it calls.
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Set the hook running flag, and restore the previous flag when dropped.
+struct HookGuard(bool);
+
+impl HookGuard {
+    fn set(in_hook: bool) -> Self {
+        Self(IN_HOOK.replace(in_hook))
+    }
+}
+
+impl Drop for HookGuard {
+    fn drop(&mut self) {
+        IN_HOOK.set(self.0);
+    }
+}
+
+/// The user function call passed to the hook registered by
+/// [Module::on_execute_ex](crate::modules::Module::on_execute_ex).
+///
+/// The function is executed by the previous `zend_execute_ex` exactly once,
+/// either by [ExecuteEx::call], or when it's dropped without being called.
+pub struct ExecuteEx<'a> {
+    execute_data: &'a mut ExecuteData,
+    inner: RawExecuteEx,
+    executed: bool,
+}
+
+impl ExecuteEx<'_> {
+    /// Get the execute data of the function to be executed.
+    pub fn execute_data(&self) -> &ExecuteData {
+        self.execute_data
+    }
+
+    /// Get the mutable execute data of the function to be executed.
+    pub fn execute_data_mut(&mut self) -> &mut ExecuteData {
+        self.execute_data
+    }
+
+    /// Execute the user function by calling the previous `zend_execute_ex`.
+    ///
+    /// The call frame of the function is released after that, so the execute
+    /// data can't be accessed anymore.
+    pub fn call(mut self) {
+        self.execute();
+    }
+
+    fn execute(&mut self) {
+        if replace(&mut self.executed, true) {
+            return;
+        }
+        let _guard = HookGuard::set(false);
+        unsafe { (self.inner)(self.execute_data.as_mut_ptr()) }
+    }
+}
+
+impl Drop for ExecuteEx<'_> {
+    fn drop(&mut self) {
+        self.execute();
+    }
+}
+
+/// The internal function call passed to the hook registered by
+/// [Module::on_execute_internal](crate::modules::Module::on_execute_internal).
+///
+/// The function is executed by the previous `zend_execute_internal` (or
+/// `execute_internal` if there is no previous one) exactly once, either by
+/// [ExecuteInternal::call], or when it's dropped without being called.
+pub struct ExecuteInternal<'a> {
+    execute_data: &'a mut ExecuteData,
+    return_value: *mut zval,
+    inner: Option<RawExecuteInternal>,
+    executed: bool,
+}
+
+impl<'a> ExecuteInternal<'a> {
+    /// Get the execute data of the function to be executed.
+    pub fn execute_data(&self) -> &ExecuteData {
+        self.execute_data
+    }
+
+    /// Get the mutable execute data of the function to be executed.
+    pub fn execute_data_mut(&mut self) -> &mut ExecuteData {
+        self.execute_data
+    }
+
+    /// Execute the internal function, and return the return value of it,
+    /// which can be modified.
+    pub fn call(mut self) -> &'a mut ZVal {
+        self.execute();
+        unsafe { ZVal::from_mut_ptr(self.return_value) }
+    }
+
+    fn execute(&mut self) {
+        if replace(&mut self.executed, true) {
+            return;
+        }
+        let _guard = HookGuard::set(false);
+        unsafe {
+            raw_execute_internal(
+                self.inner,
+                self.execute_data.as_mut_ptr(),
+                self.return_value,
+            )
+        }
+    }
+}
+
+impl Drop for ExecuteInternal<'_> {
+    fn drop(&mut self) {
+        self.execute();
+    }
+}
+
+/// Registered hooks and the pointers they replaced.
+#[derive(Default)]
+pub(crate) struct ExecuteHooks {
+    pub(crate) execute_ex: Option<Box<ExecuteExHook>>,
+    pub(crate) execute_internal: Option<Box<ExecuteInternalHook>>,
+    previous_execute_ex: Option<RawExecuteEx>,
+    previous_execute_internal: Option<RawExecuteInternal>,
+}
+
+impl ExecuteHooks {
+    /// Replace the global pointers, called in `MINIT`.
+    pub(crate) unsafe fn install(&mut self) {
+        unsafe {
+            if self.execute_ex.is_some() {
+                self.previous_execute_ex = zend_execute_ex;
+                zend_execute_ex = Some(hooked_execute_ex);
+            }
+            if self.execute_internal.is_some() {
+                self.previous_execute_internal = zend_execute_internal;
+                zend_execute_internal = Some(hooked_execute_internal);
+            }
+        }
+    }
+
+    /// Restore the global pointers, called in `MSHUTDOWN`.
+    pub(crate) unsafe fn uninstall(&mut self) {
+        unsafe {
+            if self.execute_ex.is_some() {
+                zend_execute_ex = self.previous_execute_ex.take();
+            }
+            if self.execute_internal.is_some() {
+                zend_execute_internal = self.previous_execute_internal.take();
+            }
+        }
+    }
+}
+
+/// Reset the re-entrance flag, in case the previous request bailed out
+/// inside the hook.
+pub(crate) fn reset() {
+    IN_HOOK.set(false);
+}
+
+unsafe fn raw_execute_internal(
+    previous: Option<RawExecuteInternal>, execute_data: *mut zend_execute_data,
+    return_value: *mut zval,
+) {
+    unsafe {
+        match previous {
+            Some(f) => f(execute_data, return_value),
+            None => execute_internal(execute_data, return_value),
+        }
+    }
+}
+
+unsafe extern "C" fn hooked_execute_ex(execute_data: *mut zend_execute_data) {
+    unsafe {
+        let hooks = &global_module().execute_hooks;
+        let previous = hooks.previous_execute_ex.unwrap_or(execute_ex);
+
+        match &hooks.execute_ex {
+            Some(hook) if !IN_HOOK.get() => {
+                let _guard = HookGuard::set(true);
+                hook(ExecuteEx {
+                    execute_data: ExecuteData::from_mut_ptr(execute_data),
+                    inner: previous,
+                    executed: false,
+                });
+            }
+            _ => previous(execute_data),
+        }
+    }
+}
+
+unsafe extern "C" fn hooked_execute_internal(
+    execute_data: *mut zend_execute_data, return_value: *mut zval,
+) {
+    unsafe {
+        let hooks = &global_module().execute_hooks;
+        let previous = hooks.previous_execute_internal;
+
+        match &hooks.execute_internal {
+            Some(hook) if !IN_HOOK.get() => {
+                let _guard = HookGuard::set(true);
+                hook(ExecuteInternal {
+                    execute_data: ExecuteData::from_mut_ptr(execute_data),
+                    return_value,
+                    inner: previous,
+                    executed: false,
+                });
+            }
+            _ => raw_execute_internal(previous, execute_data, return_value),
+        }
+    }
+}
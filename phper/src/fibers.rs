@@ -0,0 +1,170 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to [zend_fiber], only supported since PHP 8.1.
+//!
+//! The Rust handler called inside the fiber can suspend the fiber by
+//! [ZFiber::suspend], the Rust frames are kept on the stack of the fiber until
+//! the fiber is resumed, so don't hold any borrows (such as `RefCell`) shared
+//! with the outside across the suspension.
+#![cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+
+use crate::{
+    alloc::ToRefOwned,
+    classes::ClassEntry,
+    functions::call,
+    objects::{ZObj, ZObject},
+    sys::*,
+    values::ZVal,
+};
+use std::fmt::{self, Debug};
+
+/// Predefined class `Fiber`.
+#[inline]
+pub fn fiber_class<'a>() -> &'a ClassEntry {
+    unsafe { ClassEntry::from_ptr(zend_ce_fiber) }
+}
+
+/// Wrapper of the PHP `Fiber` object.
+///
+/// The exceptions thrown in the fiber, and the `FiberError` for the invalid
+/// operations, are returned as [crate::Error].
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{fibers::ZFiber, values::ZVal};
+///
+/// fn drive(callable: ZVal) -> phper::Result<ZVal> {
+///     let mut fiber = ZFiber::new(callable)?;
+///     let mut value = fiber.start([])?;
+///     while !fiber.is_terminated() {
+///         value = fiber.resume(value)?;
+///     }
+///     fiber.get_return()
+/// }
+/// ```
+pub struct ZFiber {
+    inner: ZObject,
+}
+
+impl ZFiber {
+    /// Create the fiber with the callable, like `new Fiber($callable)`.
+    pub fn new(callable: impl Into<ZVal>) -> crate::Result<Self> {
+        let inner = fiber_class().new_object([callable.into()])?;
+        Ok(Self { inner })
+    }
+
+    /// Wrap the object if it is the instance of `Fiber`.
+    pub fn from_z_obj(obj: &mut ZObj) -> Option<Self> {
+        obj.get_class().is_instance_of(fiber_class()).then(|| Self {
+            inner: obj.to_ref_owned(),
+        })
+    }
+
+    /// Get the currently executing fiber, like `Fiber::getCurrent()`, return
+    /// `None` if called outside of fibers.
+    pub fn current() -> Option<Self> {
+        unsafe {
+            ZObj::try_from_mut_ptr(phper_zend_fiber_active()).map(|obj| Self {
+                inner: obj.to_ref_owned(),
+            })
+        }
+    }
+
+    /// Suspend the currently executing fiber with the value, like
+    /// `Fiber::suspend($value)`, return the value passed to
+    /// [ZFiber::resume] when the fiber is resumed.
+    ///
+    /// The `FiberError` is returned if called outside of fibers.
+    pub fn suspend(value: impl Into<ZVal>) -> crate::Result<ZVal> {
+        call("Fiber::suspend", [value.into()])
+    }
+
+    /// Start the fiber with the arguments passed to the callable, return the
+    /// value of the first suspension, or `null` if the fiber returns.
+    pub fn start(&mut self, arguments: impl AsMut<[ZVal]>) -> crate::Result<ZVal> {
+        self.inner.call("start", arguments)
+    }
+
+    /// Resume the fiber with the value returned by the suspension, return the
+    /// value of the next suspension, or `null` if the fiber returns.
+    pub fn resume(&mut self, value: impl Into<ZVal>) -> crate::Result<ZVal> {
+        self.inner.call("resume", [value.into()])
+    }
+
+    /// Resume the fiber by throwing the exception from the suspension.
+    pub fn throw(&mut self, exception: ZObject) -> crate::Result<ZVal> {
+        self.inner.call("throw", [exception.into()])
+    }
+
+    /// Get the value returned by the callable of the terminated fiber.
+    pub fn get_return(&mut self) -> crate::Result<ZVal> {
+        self.inner.call("getReturn", [])
+    }
+
+    /// Returns true if the fiber has been started.
+    pub fn is_started(&self) -> bool {
+        unsafe { phper_zend_fiber_is_started(self.inner.as_ptr()) }
+    }
+
+    /// Returns true if the fiber is suspended.
+    pub fn is_suspended(&self) -> bool {
+        unsafe { phper_zend_fiber_is_suspended(self.inner.as_ptr()) }
+    }
+
+    /// Returns true if the fiber is running, including the fiber which
+    /// starts or resumes another fiber.
+    pub fn is_running(&self) -> bool {
+        unsafe { phper_zend_fiber_is_running(self.inner.as_ptr()) }
+    }
+
+    /// Returns true if the fiber has returned or thrown.
+    pub fn is_terminated(&self) -> bool {
+        unsafe { phper_zend_fiber_is_terminated(self.inner.as_ptr()) }
+    }
+
+    /// Gets the `Fiber` object.
+    #[inline]
+    pub fn as_z_obj(&self) -> &ZObj {
+        &self.inner
+    }
+
+    /// Converts into the `Fiber` object.
+    #[inline]
+    pub fn into_z_object(self) -> ZObject {
+        self.inner
+    }
+}
+
+impl Clone for ZFiber {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.new_ref(),
+        }
+    }
+}
+
+impl Debug for ZFiber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZFiber")
+            .field("started", &self.is_started())
+            .field("suspended", &self.is_suspended())
+            .field("running", &self.is_running())
+            .field("terminated", &self.is_terminated())
+            .finish()
+    }
+}
+
+impl From<ZFiber> for ZVal {
+    fn from(fiber: ZFiber) -> Self {
+        fiber.inner.into()
+    }
+}
@@ -12,11 +12,14 @@
 //!
 //! TODO Add support for closures / lambda functions.
 
+#[cfg(phper_major_version = "8")]
+use crate::attributes::Attribute;
 use crate::{
     classes::{ClassEntry, RawVisibility, Visibility},
     errors::{ArgumentCountError, ExceptionGuard, ThrowObject, Throwable, throw},
     modules::global_module,
     objects::{StateObj, ZObj, ZObject},
+    output::{LogLevel, log},
     strings::{ZStr, ZString},
     sys::*,
     types::{ArgumentTypeHint, ReturnTypeHint},
@@ -28,7 +31,7 @@ use std::{
     collections::HashMap,
     ffi::{CStr, CString},
     marker::PhantomData,
-    mem::{ManuallyDrop, transmute, zeroed},
+    mem::{ManuallyDrop, replace, take, transmute, zeroed},
     ptr::null_mut,
     rc::Rc,
 };
@@ -36,6 +39,10 @@ use std::{
 /// Used to find the handler in the invoke function.
 pub(crate) type HandlerMap = HashMap<(Option<CString>, CString), Rc<dyn Callable>>;
 
+/// Used to find the handler of trait methods copied into other classes, keyed
+/// by the arg info pointer, which is kept by the copies.
+pub(crate) type TraitHandlerMap = HashMap<*const zend_internal_arg_info, Rc<dyn Callable>>;
+
 pub(crate) trait Callable {
     fn call(&self, execute_data: &mut ExecuteData, arguments: &mut [ZVal], return_value: &mut ZVal);
 }
@@ -132,6 +139,38 @@ where
     }
 }
 
+pub(crate) struct TraitMethod<F, Z, E>(F, PhantomData<(Z, E)>);
+
+impl<F, Z, E> TraitMethod<F, Z, E> {
+    pub(crate) fn new(f: F) -> Self {
+        Self(f, PhantomData)
+    }
+}
+
+impl<F, Z, E> Callable for TraitMethod<F, Z, E>
+where
+    F: Fn(&mut ZObj, &mut [ZVal]) -> Result<Z, E>,
+    Z: Into<ZVal>,
+    E: Throwable,
+{
+    fn call(
+        &self, execute_data: &mut ExecuteData, arguments: &mut [ZVal], return_value: &mut ZVal,
+    ) {
+        let this = execute_data.get_this_mut().unwrap();
+        match (self.0)(this, arguments) {
+            Ok(z) => {
+                *return_value = z.into();
+            }
+            Err(e) => {
+                unsafe {
+                    throw(e);
+                }
+                *return_value = ().into();
+            }
+        }
+    }
+}
+
 /// Wrapper of [`zend_function_entry`].
 #[repr(transparent)]
 pub struct FunctionEntry {
@@ -326,6 +365,8 @@ pub struct FunctionEntity {
     pub(crate) handler: Rc<dyn Callable>,
     arguments: Vec<Argument>,
     return_type: Option<ReturnType>,
+    #[cfg(phper_major_version = "8")]
+    attributes: Vec<Attribute>,
 }
 
 impl FunctionEntity {
@@ -336,6 +377,8 @@ impl FunctionEntity {
             handler,
             arguments: Default::default(),
             return_type: None,
+            #[cfg(phper_major_version = "8")]
+            attributes: Default::default(),
         }
     }
 
@@ -359,6 +402,23 @@ impl FunctionEntity {
         self.return_type = Some(return_type);
         self
     }
+
+    /// Add attribute to the function.
+    #[cfg(phper_major_version = "8")]
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Add the attributes to the registered function, called in `MINIT`.
+    #[cfg(phper_major_version = "8")]
+    pub(crate) unsafe fn register_attributes(&self) {
+        unsafe {
+            let func = find_function(crate::cg!(function_table), &self.name);
+            add_attributes(func, &self.attributes, &self.arguments);
+        }
+    }
 }
 
 /// Builder for registering class method.
@@ -368,6 +428,8 @@ pub struct MethodEntity {
     arguments: Vec<Argument>,
     visibility: RawVisibility,
     return_type: Option<ReturnType>,
+    #[cfg(phper_major_version = "8")]
+    attributes: Vec<Attribute>,
 }
 
 impl MethodEntity {
@@ -381,6 +443,8 @@ impl MethodEntity {
             visibility: visibility as RawVisibility,
             arguments: Default::default(),
             return_type: None,
+            #[cfg(phper_major_version = "8")]
+            attributes: Default::default(),
         }
     }
 
@@ -416,6 +480,64 @@ impl MethodEntity {
         self.return_type = Some(return_type);
         self
     }
+
+    /// Mark the method as `final`, which can't be overridden by the subclass.
+    #[inline]
+    pub fn set_final(&mut self) -> &mut Self {
+        self.visibility |= ZEND_ACC_FINAL;
+        self
+    }
+
+    /// Mark the method as deprecated, calling it will emit `E_DEPRECATED`.
+    #[inline]
+    pub fn set_deprecated(&mut self) -> &mut Self {
+        self.visibility |= ZEND_ACC_DEPRECATED;
+        self
+    }
+
+    /// Add attribute to the method.
+    #[cfg(phper_major_version = "8")]
+    #[inline]
+    pub fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Add the attributes to the registered method of the class.
+    #[cfg(phper_major_version = "8")]
+    pub(crate) unsafe fn register_attributes(&self, ce: *mut zend_class_entry) {
+        unsafe {
+            let func = find_function(&(*ce).function_table, &self.name);
+            add_attributes(func, &self.attributes, &self.arguments);
+        }
+    }
+}
+
+#[cfg(phper_major_version = "8")]
+unsafe fn find_function(function_table: *const HashTable, name: &CStr) -> *mut zend_function {
+    unsafe {
+        let name = name.to_bytes().to_ascii_lowercase();
+        phper_zend_hash_str_find_ptr(function_table, name.as_ptr().cast(), name.len()).cast()
+    }
+}
+
+#[cfg(phper_major_version = "8")]
+unsafe fn add_attributes(
+    func: *mut zend_function, attributes: &[Attribute], arguments: &[Argument],
+) {
+    unsafe {
+        let Some(func) = func.as_mut() else {
+            return;
+        };
+        for attribute in attributes {
+            attribute.add_to(&mut func.common.attributes, 0);
+        }
+        for (offset, argument) in arguments.iter().enumerate() {
+            for attribute in &argument.attributes {
+                attribute.add_to(&mut func.common.attributes, offset as u32 + 1);
+            }
+        }
+    }
 }
 
 /// Function or method argument info.
@@ -426,6 +548,8 @@ pub struct Argument {
     required: bool,
     nullable: bool,
     default_value: Option<CString>,
+    #[cfg(phper_major_version = "8")]
+    attributes: Vec<Attribute>,
 }
 
 impl Argument {
@@ -439,6 +563,8 @@ impl Argument {
             required: true,
             nullable: false,
             default_value: None,
+            #[cfg(phper_major_version = "8")]
+            attributes: Vec::new(),
         }
     }
 
@@ -474,6 +600,13 @@ impl Argument {
         self.required = false; // arg with default value does not count towards required arg count
         self
     }
+
+    /// Add attribute to the argument, such as `SensitiveParameter`.
+    #[cfg(phper_major_version = "8")]
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
 }
 
 /// Function or method return type.
@@ -740,6 +873,14 @@ unsafe extern "C" fn invoke(execute_data: *mut zend_execute_data, return_value:
                         .handler_map
                         .get(&(class_name, function_name))
                 })
+                .or_else(|| {
+                    // Trait methods are copied into the using classes, maybe
+                    // with alias, so find them by the arg info.
+                    let arg_info = (*execute_data.func().as_ptr()).internal_function.arg_info;
+                    global_module()
+                        .trait_handler_map
+                        .get(&arg_info.cast_const())
+                })
                 .expect("invoke handler is not correct")
                 .as_ref()
         };
@@ -772,6 +913,222 @@ unsafe extern "C" fn invoke(execute_data: *mut zend_execute_data, return_value:
     }
 }
 
+type RawHandler = unsafe extern "C" fn(*mut zend_execute_data, *mut zval);
+
+#[cfg(phper_zts)]
+pub(crate) type ReplaceHook = dyn Fn(OriginalHandler<'_>) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+pub(crate) type ReplaceHook = dyn Fn(OriginalHandler<'_>);
+
+/// The call of the function replaced by
+/// [Module::replace_function](crate::modules::Module::replace_function) or
+/// [Module::replace_method](crate::modules::Module::replace_method).
+///
+/// The original handler is executed exactly once, either by
+/// [OriginalHandler::call], or when it's dropped without being called.
+pub struct OriginalHandler<'a> {
+    execute_data: &'a mut ExecuteData,
+    return_value: *mut zval,
+    inner: RawHandler,
+    executed: bool,
+}
+
+impl<'a> OriginalHandler<'a> {
+    /// Get the execute data of the replaced function.
+    pub fn execute_data(&self) -> &ExecuteData {
+        self.execute_data
+    }
+
+    /// Get the mutable execute data of the replaced function.
+    pub fn execute_data_mut(&mut self) -> &mut ExecuteData {
+        self.execute_data
+    }
+
+    /// Execute the function by calling the original handler, and return the
+    /// return value of it, which can be modified.
+    pub fn call(mut self) -> &'a mut ZVal {
+        self.execute();
+        unsafe { ZVal::from_mut_ptr(self.return_value) }
+    }
+
+    fn execute(&mut self) {
+        if replace(&mut self.executed, true) {
+            return;
+        }
+        unsafe { (self.inner)(self.execute_data.as_mut_ptr(), self.return_value) }
+    }
+}
+
+impl Drop for OriginalHandler<'_> {
+    fn drop(&mut self) {
+        self.execute();
+    }
+}
+
+/// Function or method to be replaced in `MINIT`.
+pub(crate) struct FunctionReplacement {
+    class_name: Option<String>,
+    function_name: String,
+    hook: Box<ReplaceHook>,
+}
+
+impl FunctionReplacement {
+    pub(crate) fn new(
+        class_name: Option<String>, function_name: impl Into<String>, hook: Box<ReplaceHook>,
+    ) -> Self {
+        Self {
+            class_name,
+            function_name: function_name.into(),
+            hook,
+        }
+    }
+
+    fn display_name(&self) -> String {
+        match &self.class_name {
+            Some(class_name) => format!("{}::{}", class_name, self.function_name),
+            None => self.function_name.clone(),
+        }
+    }
+
+    #[allow(clippy::useless_conversion)]
+    unsafe fn find_function(&self) -> *mut zend_function {
+        unsafe {
+            let function_table = match &self.class_name {
+                Some(class_name) => match ClassEntry::from_globals(class_name) {
+                    Ok(ce) => &(*ce.as_ptr()).function_table as *const _,
+                    Err(_) => return null_mut(),
+                },
+                None => crate::cg!(function_table) as *const _,
+            };
+            let name = self.function_name.to_lowercase();
+            phper_zend_hash_str_find_ptr(
+                function_table,
+                name.as_ptr().cast(),
+                name.len().try_into().unwrap(),
+            )
+            .cast()
+        }
+    }
+}
+
+struct ReplacedFunction {
+    func: *mut zend_function,
+    original: RawHandler,
+    hook: Box<ReplaceHook>,
+}
+
+/// The replaced functions, restored in `MSHUTDOWN`.
+#[derive(Default)]
+pub(crate) struct ReplacedFunctions {
+    replacements: Vec<FunctionReplacement>,
+    replaced: HashMap<ReplacedKey, ReplacedFunction>,
+}
+
+impl ReplacedFunctions {
+    pub(crate) fn push(&mut self, replacement: FunctionReplacement) {
+        self.replacements.push(replacement);
+    }
+
+    /// Replace the handlers of the found internal functions, called in
+    /// `MINIT`.
+    pub(crate) unsafe fn install(&mut self) {
+        unsafe {
+            for replacement in take(&mut self.replacements) {
+                let func = replacement.find_function();
+                if func.is_null() {
+                    log(
+                        LogLevel::Warning,
+                        format!(
+                            "can't replace {}, function not found",
+                            replacement.display_name()
+                        ),
+                    );
+                    continue;
+                }
+
+                if u32::from((*func).type_) != ZEND_INTERNAL_FUNCTION {
+                    log(
+                        LogLevel::Warning,
+                        format!(
+                            "can't replace {}, not an internal function",
+                            replacement.display_name()
+                        ),
+                    );
+                    continue;
+                }
+
+                let key = replaced_key(func);
+                if self.replaced.contains_key(&key) {
+                    log(
+                        LogLevel::Warning,
+                        format!(
+                            "can't replace {}, function already replaced",
+                            replacement.display_name()
+                        ),
+                    );
+                    continue;
+                }
+
+                let Some(original) = (*func).internal_function.handler else {
+                    continue;
+                };
+                (*func).internal_function.handler = Some(invoke_replaced);
+
+                self.replaced.insert(
+                    key,
+                    ReplacedFunction {
+                        func,
+                        original,
+                        hook: replacement.hook,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Restore the original handlers, called in `MSHUTDOWN`.
+    pub(crate) unsafe fn uninstall(&mut self) {
+        unsafe {
+            for (_, replaced) in self.replaced.drain() {
+                (*replaced.func).internal_function.handler = Some(replaced.original);
+            }
+        }
+    }
+}
+
+/// Methods inherited by subclasses are copies of the parent one, which share
+/// the scope and the name string with it, so the replaced function is keyed by
+/// them rather than the function pointer.
+type ReplacedKey = (*const zend_class_entry, *const zend_string);
+
+unsafe fn replaced_key(func: *const zend_function) -> ReplacedKey {
+    unsafe { ((*func).common.scope, (*func).common.function_name) }
+}
+
+/// The entry for all replaced functions.
+unsafe extern "C" fn invoke_replaced(
+    execute_data: *mut zend_execute_data, return_value: *mut zval,
+) {
+    unsafe {
+        let execute_data = ExecuteData::from_mut_ptr(execute_data);
+
+        let key = replaced_key(execute_data.func().as_ptr());
+        let Some(replaced) = global_module().replaced_functions.replaced.get(&key) else {
+            throw(crate::Error::boxed("the replaced function is not found"));
+            *ZVal::from_mut_ptr(return_value) = ().into();
+            return;
+        };
+
+        (replaced.hook)(OriginalHandler {
+            execute_data,
+            return_value,
+            inner: replaced.original,
+            executed: false,
+        });
+    }
+}
+
 /// Call user function by name.
 ///
 /// # Examples
@@ -0,0 +1,118 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the global symbol table `EG(symbol_table)` and the
+//! superglobals, like `$_SERVER`.
+//!
+//! The auto globals `$_SERVER`, `$_ENV` and `$_REQUEST` are created just in
+//! time by default (see `auto_globals_jit`), the functions here arm them
+//! before looking up, so they are always available.
+
+use crate::{
+    arrays::{ZArr, ZArray},
+    sys::*,
+    values::ZVal,
+};
+use std::mem::ManuallyDrop;
+
+/// Get the global symbol table, same as PHP `$GLOBALS`.
+///
+/// Notice that the values of the global variables declared in the main script
+/// are stored as indirect slots, prefer [get] and [set_global] to access them.
+///
+/// # Safety
+///
+/// The returned reference is unbounded, the caller must make sure that there
+/// is no other reference to the symbol table or its values alive while the
+/// returned one is used, such as the ones returned by [get_mut].
+pub unsafe fn symbol_table<'a>() -> &'a mut ZArr {
+    unsafe { ZArr::from_mut_ptr(&mut crate::eg!(symbol_table)) }
+}
+
+/// Get the copy of global variable by name (without `$`), the auto global
+/// like `_SERVER` will be armed if it hasn't been created yet.
+///
+/// References are dereferenced, returns `None` if the variable isn't defined.
+///
+/// The value is copied, because the variable may be replaced or destroyed by
+/// [set_global] or PHP code after that.
+pub fn get(name: &str) -> Option<ZVal> {
+    unsafe { ZVal::try_from_ptr(find(name)).cloned() }
+}
+
+/// Get the mutable global variable by name (without `$`), the auto global
+/// like `_SERVER` will be armed if it hasn't been created yet.
+///
+/// # Safety
+///
+/// The returned reference is unbounded, the caller must make sure that there
+/// is no other reference to the same variable alive while the returned one is
+/// used, such as calling this function twice with the same name.
+pub unsafe fn get_mut<'a>(name: &str) -> Option<&'a mut ZVal> {
+    unsafe { ZVal::try_from_mut_ptr(find(name)) }
+}
+
+fn find(name: &str) -> *mut zval {
+    unsafe {
+        phper_zend_is_auto_global_str(name.as_ptr().cast(), name.len());
+        phper_zend_global_var_find(name.as_ptr().cast(), name.len())
+    }
+}
+
+/// Set the global variable by name (without `$`), same as PHP
+/// `$GLOBALS[$name] = $value`.
+///
+/// If the variable is a reference, the referenced value will be assigned.
+pub fn set_global(name: &str, value: impl Into<ZVal>) {
+    let mut value = ManuallyDrop::new(value.into());
+    unsafe {
+        phper_zend_is_auto_global_str(name.as_ptr().cast(), name.len());
+        phper_zend_global_var_update(name.as_ptr().cast(), name.len(), value.as_mut_ptr());
+    }
+}
+
+/// Get the copy of superglobal `$_GET`.
+pub fn get_vars() -> ZArray {
+    track_vars("_GET", TRACK_VARS_GET)
+}
+
+/// Get the copy of superglobal `$_POST`.
+pub fn post_vars() -> ZArray {
+    track_vars("_POST", TRACK_VARS_POST)
+}
+
+/// Get the copy of superglobal `$_COOKIE`.
+pub fn cookie_vars() -> ZArray {
+    track_vars("_COOKIE", TRACK_VARS_COOKIE)
+}
+
+/// Get the copy of superglobal `$_SERVER`.
+pub fn server_vars() -> ZArray {
+    track_vars("_SERVER", TRACK_VARS_SERVER)
+}
+
+/// Get the copy of superglobal `$_ENV`.
+pub fn env_vars() -> ZArray {
+    track_vars("_ENV", TRACK_VARS_ENV)
+}
+
+/// Copy the superglobal from the symbol table, or the original one in
+/// `PG(http_globals)` if it has been replaced by non array value.
+fn track_vars(name: &str, track: u32) -> ZArray {
+    unsafe {
+        let arr = match ZVal::try_from_ptr(find(name)).and_then(ZVal::as_z_arr) {
+            Some(arr) => arr,
+            None => ZVal::from_ptr(&crate::pg!(http_globals)[track as usize])
+                .as_z_arr()
+                .expect("http globals should be array"),
+        };
+        arr.to_owned()
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the `MINFO` of module, shown by `phpinfo()` and
+//! `php --ri <EXTENSION_NAME>`.
+
+use crate::{
+    output::echo,
+    sys::*,
+    utils::{ensure_end_with_zero, escape_html},
+};
+
+/// Builder of the extension information, passed to the hook registered by
+/// [Module::on_info](crate::modules::Module::on_info).
+///
+/// The tables are rendered after the hook returns, as HTML for `phpinfo()`
+/// in web, and as text for CLI.
+#[derive(Default)]
+pub struct InfoBuilder {
+    tables: Vec<InfoTable>,
+    hide_ini_entries: bool,
+}
+
+impl InfoBuilder {
+    /// Add a new table.
+    pub fn table(&mut self) -> &mut InfoTable {
+        self.tables.push(Default::default());
+        self.tables.last_mut().unwrap()
+    }
+
+    /// Don't display the ini entries of the module after the tables.
+    pub fn hide_ini_entries(&mut self) {
+        self.hide_ini_entries = true;
+    }
+
+    pub(crate) fn is_ini_entries_hidden(&self) -> bool {
+        self.hide_ini_entries
+    }
+
+    pub(crate) fn render(&self) {
+        for table in &self.tables {
+            table.render();
+        }
+    }
+}
+
+enum InfoRow {
+    Header(Vec<String>),
+    ColspanHeader(usize, String),
+    Row(Vec<String>),
+}
+
+/// Table of the extension information.
+#[derive(Default)]
+pub struct InfoTable {
+    rows: Vec<InfoRow>,
+}
+
+impl InfoTable {
+    /// Add header row.
+    pub fn header(&mut self, columns: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.rows.push(InfoRow::Header(
+            columns.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
+    /// Add header spans `colspan` columns.
+    pub fn colspan_header(&mut self, colspan: usize, header: impl Into<String>) -> &mut Self {
+        self.rows
+            .push(InfoRow::ColspanHeader(colspan, header.into()));
+        self
+    }
+
+    /// Add row, the first column is the key, and the others are the values,
+    /// the empty value is shown as `no value` in html, and as a space in
+    /// text, same as `php_info_print_table_row`.
+    pub fn row(&mut self, columns: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.rows
+            .push(InfoRow::Row(columns.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    fn render(&self) {
+        unsafe {
+            php_info_print_table_start();
+        }
+        for row in &self.rows {
+            match row {
+                InfoRow::Header(columns) => print_header(columns),
+                InfoRow::ColspanHeader(colspan, header) => {
+                    let header = ensure_end_with_zero(header.as_str());
+                    unsafe {
+                        php_info_print_table_colspan_header(*colspan as _, header.as_ptr());
+                    }
+                }
+                InfoRow::Row(columns) => print_row(columns),
+            }
+        }
+        unsafe {
+            php_info_print_table_end();
+        }
+    }
+}
+
+fn as_text() -> bool {
+    unsafe { sapi_module.phpinfo_as_text != 0 }
+}
+
+/// Same as `php_info_print_table_header`, which is variadic.
+fn print_header(columns: &[String]) {
+    if as_text() {
+        echo(format!("{}\n", columns.join(" => ")));
+    } else {
+        let mut html = String::from("<tr class=\"h\">");
+        for column in columns {
+            let column = if column.is_empty() { " " } else { column };
+            html.push_str(&format!("<th>{}</th>", escape_html(column)));
+        }
+        html.push_str("</tr>\n");
+        echo(html);
+    }
+}
+
+/// Same as `php_info_print_table_row`, which is variadic.
+fn print_row(columns: &[String]) {
+    if as_text() {
+        let columns = columns
+            .iter()
+            .map(|column| if column.is_empty() { " " } else { column })
+            .collect::<Vec<_>>();
+        echo(format!("{}\n", columns.join(" => ")));
+    } else {
+        let mut html = String::from("<tr>");
+        for (i, column) in columns.iter().enumerate() {
+            let class = if i == 0 { "e" } else { "v" };
+            if column.is_empty() {
+                html.push_str(&format!("<td class=\"{}\"><i>no value</i> </td>", class));
+            } else {
+                html.push_str(&format!(
+                    "<td class=\"{}\">{} </td>",
+                    class,
+                    escape_html(column)
+                ));
+            }
+        }
+        html.push_str("</tr>\n");
+        echo(html);
+    }
+}
@@ -10,15 +10,39 @@
 
 //! Apis relate to [zend_ini_entry_def].
 
-use crate::sys::*;
+use crate::{
+    errors::InvalidIniValueError,
+    output::{LogLevel, echo, log},
+    strings::ZStr,
+    sys::*,
+    utils::escape_html,
+};
 use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
     ffi::{CStr, c_int},
+    fmt::Display,
+    marker::PhantomData,
     mem::zeroed,
-    os::raw::c_char,
+    os::raw::{c_char, c_void},
     ptr::null_mut,
-    str,
+    str::{self, FromStr},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
 };
 
+thread_local! {
+    /// The parsed values of the bound ini entries, per thread because the ini
+    /// entries are copied per thread for ZTS.
+    static BOUND_VALUES: RefCell<HashMap<usize, Box<dyn Any>>> = Default::default();
+}
+
+static BOUND_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// Get the global registered configuration value.
 ///
 /// # Examples
@@ -131,10 +155,235 @@ impl FromIniValue for Option<&CStr> {
     }
 }
 
-pub(crate) struct IniEntity {
+impl FromIniValue for Option<String> {
+    fn from_ini_value(name: &str) -> Self {
+        <Option<&CStr>>::from_ini_value(name).map(|s| s.to_string_lossy().into_owned())
+    }
+}
+
+impl FromIniValue for String {
+    fn from_ini_value(name: &str) -> Self {
+        <Option<String>>::from_ini_value(name).unwrap_or_default()
+    }
+}
+
+/// Comma separated list, the items are trimmed and the empty items are
+/// skipped.
+impl FromIniValue for Vec<String> {
+    fn from_ini_value(name: &str) -> Self {
+        parse_list(&String::from_ini_value(name))
+    }
+}
+
+/// Parsed by [parse_duration], zero if the value is invalid.
+impl FromIniValue for Duration {
+    fn from_ini_value(name: &str) -> Self {
+        parse_duration(&String::from_ini_value(name)).unwrap_or_default()
+    }
+}
+
+/// Parsed by [Quantity::from_str], zero if the value is invalid.
+impl FromIniValue for Quantity {
+    fn from_ini_value(name: &str) -> Self {
+        String::from_ini_value(name).parse().unwrap_or_default()
+    }
+}
+
+/// Byte size quantity, like `memory_limit`, supports the suffixes `K`, `M` and
+/// `G`.
+///
+/// Parsed by `zend_ini_parse_quantity` since PHP 8.2, which also supports the
+/// `0x`, `0o` and `0b` prefixes, and by `zend_atol` for previous versions,
+/// which never rejects the value.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::ini::Quantity;
+///
+/// let quantity = "128M".parse::<Quantity>().unwrap();
+/// assert_eq!(quantity.bytes(), 128 * 1024 * 1024);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quantity(i64);
+
+impl Quantity {
+    /// Construct from bytes.
+    #[inline]
+    pub const fn new(bytes: i64) -> Self {
+        Self(bytes)
+    }
+
+    /// Get the bytes.
+    #[inline]
+    pub const fn bytes(self) -> i64 {
+        self.0
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = InvalidIniValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut valid = false;
+        let bytes =
+            unsafe { phper_zend_ini_parse_quantity(s.as_ptr().cast(), s.len(), &mut valid) };
+        if valid {
+            Ok(Self(bytes))
+        } else {
+            Err(InvalidIniValueError::new(s.to_owned()))
+        }
+    }
+}
+
+impl IntoIniValue for Quantity {
+    #[inline]
+    fn into_ini_value(self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Parse the duration, the value is number of seconds, or number with the
+/// suffixes `ms`, `s`, `m` and `h`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::ini::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+/// assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+/// assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+/// assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+/// ```
+pub fn parse_duration(value: &str) -> Result<Duration, InvalidIniValueError> {
+    let trimmed = value.trim();
+    let (number, unit) = match trimmed.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(index) => trimmed.split_at(index),
+        None => (trimmed, "s"),
+    };
+    let seconds = match unit {
+        "ms" => 0.001,
+        "s" => 1.,
+        "m" => 60.,
+        "h" => 3600.,
+        _ => return Err(InvalidIniValueError::new(value.to_owned())),
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * seconds).ok())
+        .ok_or_else(|| InvalidIniValueError::new(value.to_owned()))
+}
+
+/// Parse the comma separated list, the items are trimmed and the empty items
+/// are skipped.
+pub fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+#[cfg(phper_zts)]
+type IniDisplayer = dyn Fn(&str) -> String + Send + Sync;
+
+#[cfg(not(phper_zts))]
+type IniDisplayer = dyn Fn(&str) -> String;
+
+/// Holds the displayer, boxed to be passed as `mh_arg2`.
+struct IniDisplay(Box<IniDisplayer>);
+
+#[cfg(phper_zts)]
+pub(crate) type IniParser = dyn Fn(&str) -> Result<Box<dyn Any>, String> + Send + Sync;
+
+#[cfg(not(phper_zts))]
+pub(crate) type IniParser = dyn Fn(&str) -> Result<Box<dyn Any>, String>;
+
+/// The parser of ini entry registered by
+/// [Module::add_ini_with](crate::modules::Module::add_ini_with).
+pub(crate) struct IniBound {
+    id: usize,
+    name: String,
+    default_value: String,
+    parser: Box<IniParser>,
+}
+
+impl IniBound {
+    fn parse(&self, value: &str) -> Result<Box<dyn Any>, String> {
+        (self.parser)(value)
+    }
+
+    fn store(&self, value: Box<dyn Any>) {
+        BOUND_VALUES.with_borrow_mut(|values| values.insert(self.id, value));
+    }
+}
+
+/// Typed binding of the ini entry registered by
+/// [Module::add_ini_with](crate::modules::Module::add_ini_with), holds the
+/// parsed value, which is updated when the entry is modified, so reading it
+/// doesn't need lookup and parse the string value.
+pub struct IniBinding<T> {
+    bound: Arc<IniBound>,
+    _p: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for IniBinding<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bound: self.bound.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone + 'static> IniBinding<T> {
+    /// Get the parsed value.
+    ///
+    /// # Panics
+    ///
+    /// Panic if neither the current value nor the default value can be
+    /// parsed.
+    pub fn get(&self) -> T {
+        let value = BOUND_VALUES.with_borrow(|values| {
+            values
+                .get(&self.bound.id)
+                .and_then(|value| value.downcast_ref::<T>())
+                .cloned()
+        });
+        if let Some(value) = value {
+            return value;
+        }
+
+        // The entries copied for the new thread aren't passed to `on_modify`,
+        // so parse the current value lazily.
+        let current = <Option<&CStr>>::from_ini_value(&self.bound.name)
+            .map(|value| value.to_string_lossy().into_owned());
+        let value = current
+            .and_then(|current| self.bound.parse(&current).ok())
+            .or_else(|| self.bound.parse(&self.bound.default_value).ok())
+            .expect("ini value can't be parsed");
+        let result = value
+            .downcast_ref::<T>()
+            .cloned()
+            .expect("ini value type mismatch");
+        self.bound.store(value);
+        result
+    }
+}
+
+/// Builder for registering ini entry, returned by
+/// [Module::add_ini](crate::modules::Module::add_ini).
+pub struct IniEntity {
     name: String,
     default_value: String,
     policy: Policy,
+    bound: Option<Arc<IniBound>>,
+    displayer: Option<Box<IniDisplay>>,
 }
 
 impl IniEntity {
@@ -145,12 +394,162 @@ impl IniEntity {
             name: name.into(),
             default_value: default_value.into_ini_value(),
             policy,
+            bound: None,
+            displayer: None,
         }
     }
 
-    #[inline]
+    /// Set the displayer, which transforms the value to be shown in
+    /// `phpinfo()`, such as the human-friendly size.
+    #[cfg(phper_zts)]
+    pub fn displayer(
+        &mut self, displayer: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.displayer = Some(Box::new(IniDisplay(Box::new(displayer))));
+        self
+    }
+
+    /// Set the displayer, which transforms the value to be shown in
+    /// `phpinfo()`, such as the human-friendly size.
+    #[cfg(not(phper_zts))]
+    pub fn displayer(&mut self, displayer: impl Fn(&str) -> String + 'static) -> &mut Self {
+        self.displayer = Some(Box::new(IniDisplay(Box::new(displayer))));
+        self
+    }
+
+    #[cfg(phper_zts)]
+    pub(crate) fn new_with<T, E>(
+        name: impl Into<String>, default_value: impl IntoIniValue, policy: Policy,
+        parse: impl Fn(&str) -> Result<T, E> + Send + Sync + 'static,
+    ) -> (Self, IniBinding<T>)
+    where
+        T: 'static,
+        E: Display,
+    {
+        Self::new_with_parser(
+            name,
+            default_value,
+            policy,
+            Box::new(move |value| {
+                parse(value)
+                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map_err(|e| e.to_string())
+            }),
+        )
+    }
+
+    #[cfg(not(phper_zts))]
+    pub(crate) fn new_with<T, E>(
+        name: impl Into<String>, default_value: impl IntoIniValue, policy: Policy,
+        parse: impl Fn(&str) -> Result<T, E> + 'static,
+    ) -> (Self, IniBinding<T>)
+    where
+        T: 'static,
+        E: Display,
+    {
+        Self::new_with_parser(
+            name,
+            default_value,
+            policy,
+            Box::new(move |value| {
+                parse(value)
+                    .map(|value| Box::new(value) as Box<dyn Any>)
+                    .map_err(|e| e.to_string())
+            }),
+        )
+    }
+
+    fn new_with_parser<T>(
+        name: impl Into<String>, default_value: impl IntoIniValue, policy: Policy,
+        parser: Box<IniParser>,
+    ) -> (Self, IniBinding<T>) {
+        let mut entity = Self::new(name, default_value, policy);
+        let bound = Arc::new(IniBound {
+            id: BOUND_ID.fetch_add(1, Ordering::Relaxed),
+            name: entity.name.clone(),
+            default_value: entity.default_value.clone(),
+            parser,
+        });
+        entity.bound = Some(bound.clone());
+        (
+            entity,
+            IniBinding {
+                bound,
+                _p: PhantomData,
+            },
+        )
+    }
+
     pub(crate) fn entry(&self) -> zend_ini_entry_def {
-        create_ini_entry_ex(&self.name, &self.default_value, self.policy as u32)
+        let mut entry = create_ini_entry_ex(&self.name, &self.default_value, self.policy as u32);
+        if let Some(bound) = &self.bound {
+            entry.on_modify = Some(on_modify);
+            entry.mh_arg1 = Arc::as_ptr(bound) as *mut c_void;
+        }
+        if let Some(displayer) = &self.displayer {
+            entry.displayer = Some(display);
+            entry.mh_arg2 = &**displayer as *const IniDisplay as *mut c_void;
+        }
+        entry
+    }
+}
+
+/// The `on_modify` handler of the bound entries, rejects the invalid value.
+unsafe extern "C" fn on_modify(
+    _entry: *mut zend_ini_entry, new_value: *mut zend_string, mh_arg1: *mut c_void,
+    _mh_arg2: *mut c_void, _mh_arg3: *mut c_void, _stage: c_int,
+) -> c_int {
+    unsafe {
+        let bound = &*(mh_arg1 as *const IniBound);
+        let new_value = ZStr::try_from_ptr(new_value)
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        match bound.parse(&new_value) {
+            Ok(value) => {
+                bound.store(value);
+                ZEND_RESULT_CODE_SUCCESS
+            }
+            Err(e) => {
+                log(
+                    LogLevel::Warning,
+                    format!("Invalid value \"{}\" for {}: {}", new_value, bound.name, e),
+                );
+                ZEND_RESULT_CODE_FAILURE
+            }
+        }
+    }
+}
+
+/// The `displayer` of the entries with custom displayer.
+unsafe extern "C" fn display(ini_entry: *mut zend_ini_entry, display_type: c_int) {
+    unsafe {
+        let ini_entry = &*ini_entry;
+        let value = if display_type == ZEND_INI_DISPLAY_ORIG as c_int && ini_entry.modified != 0 {
+            ini_entry.orig_value
+        } else {
+            ini_entry.value
+        };
+        let displayer = &*(ini_entry.mh_arg2 as *const IniDisplay);
+        let as_text = sapi_module.phpinfo_as_text != 0;
+
+        match ZStr::try_from_ptr(value).filter(|value| !value.to_bytes().is_empty()) {
+            Some(value) => {
+                let text = (displayer.0)(&value.to_string_lossy());
+                if as_text {
+                    echo(text);
+                } else {
+                    echo(escape_html(&text));
+                }
+            }
+            None => {
+                if as_text {
+                    echo("no value");
+                } else {
+                    echo("<i>no value</i>");
+                }
+            }
+        }
     }
 }
 
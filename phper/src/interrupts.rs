@@ -0,0 +1,122 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the PHP interrupts, such as the timeout of
+//! `max_execution_time` and `set_time_limit`, and the signals handled by
+//! `pcntl_async_signals`.
+//!
+//! PHP only checks the interrupts between the opcodes, so the long-running
+//! Rust handler should check them cooperatively by [check], or use the helpers
+//! [interruptible] and [wait].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use phper::{interrupts, modules::Module, php_get_module};
+//!
+//! #[php_get_module]
+//! pub fn get_module() -> Module {
+//!     let mut module = Module::new("hello", "0.1.0", "");
+//!
+//!     module.add_function("sum_forever", |_| {
+//!         let mut sum = 0i64;
+//!         for i in interrupts::interruptible(0..) {
+//!             sum = sum.wrapping_add(i?);
+//!         }
+//!         Ok::<_, phper::Error>(sum)
+//!     });
+//!
+//!     module
+//! }
+//! ```
+
+use crate::{errors::InterruptedError, functions::call_raw_common, sys::*};
+use std::{iter::FusedIterator, time::Duration};
+
+/// The max duration of each waiting in [wait].
+const WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Check the PHP interrupts.
+///
+/// If the execution time is exceeded, the [InterruptedError] is returned,
+/// which should be propagated by the handler, the interrupt is left pending,
+/// so the "Maximum execution time exceeded" fatal error is raised by the
+/// engine after the handler returns.
+///
+/// The other interrupts are handled in place, such as calling the signal
+/// handlers, and the exception thrown by them is returned.
+pub fn check() -> crate::Result<()> {
+    unsafe {
+        if !phper_zend_vm_interrupted() {
+            return Ok(());
+        }
+        if phper_zend_timed_out() {
+            return Err(InterruptedError.into());
+        }
+        call_raw_common(|_| phper_zend_interrupt())?;
+        Ok(())
+    }
+}
+
+/// Wrap the iterator to [check] the interrupts before yielding each item,
+/// the iteration is stopped after the error is yielded.
+pub fn interruptible<I: IntoIterator>(iter: I) -> Interruptible<I::IntoIter> {
+    Interruptible {
+        inner: Some(iter.into_iter()),
+    }
+}
+
+/// Iterator returned by [interruptible].
+pub struct Interruptible<I> {
+    inner: Option<I>,
+}
+
+impl<I: Iterator> Iterator for Interruptible<I> {
+    type Item = crate::Result<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = self.inner.as_mut()?;
+        if let Err(e) = check() {
+            self.inner = None;
+            return Some(Err(e));
+        }
+        inner.next().map(Ok)
+    }
+}
+
+impl<I: Iterator> FusedIterator for Interruptible<I> {}
+
+/// Wait in the slices, and [check] the interrupts between them.
+///
+/// The `f` should block for the given duration at most, return `Some` if
+/// finished, or `None` to continue waiting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::interrupts;
+/// use std::sync::mpsc::{Receiver, RecvTimeoutError};
+///
+/// fn recv(rx: &Receiver<String>) -> phper::Result<Option<String>> {
+///     interrupts::wait(|timeout| match rx.recv_timeout(timeout) {
+///         Ok(message) => Some(Some(message)),
+///         Err(RecvTimeoutError::Timeout) => None,
+///         Err(RecvTimeoutError::Disconnected) => Some(None),
+///     })
+/// }
+/// ```
+pub fn wait<T>(mut f: impl FnMut(Duration) -> Option<T>) -> crate::Result<T> {
+    loop {
+        check()?;
+        if let Some(output) = f(WAIT_INTERVAL) {
+            return Ok(output);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the lazy objects, only supported since PHP 8.4.
+//!
+//! The lazy objects are created by
+//! [ClassEntry::new_lazy_ghost] and [ClassEntry::new_lazy_proxy], with the
+//! initializers written in Rust, which are kept until the objects are
+//! initialized or destroyed.
+#![cfg(phper_php_8_4_or_later)]
+
+use crate::{
+    classes::ClassEntry,
+    errors::{InitializeObjectError, Throwable, throw},
+    functions::call_raw_common,
+    objects::{ZObj, ZObject},
+    sys::*,
+    values::{ExecuteData, ZVal},
+    weakrefs::ZWeakMap,
+};
+use std::{cell::RefCell, ptr::null_mut};
+
+type Initializer = Box<dyn FnOnce(&mut ZObj, &mut ZVal)>;
+
+thread_local! {
+    /// The initializers of the lazy objects created in the current request.
+    static INITIALIZERS: RefCell<Option<ZWeakMap<Initializer>>> = const { RefCell::new(None) };
+}
+
+impl ClassEntry {
+    /// Create the lazy ghost object of the class, like
+    /// `ReflectionClass::newLazyGhost()`.
+    ///
+    /// The initializer is called with the object when the state of the object
+    /// is first accessed, to initialize the properties in place. The
+    /// initializer is called at most once, if it fails, the exception is
+    /// thrown and the object can't be initialized any more.
+    ///
+    /// Only the user classes (and `stdClass`) can be lazy, the classes
+    /// registered by phper are internal classes, so the `Error` will be
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::classes::ClassEntry;
+    ///
+    /// fn lazy_service() -> phper::Result<()> {
+    ///     let class = ClassEntry::from_globals("Service")?;
+    ///     let _service = class.new_lazy_ghost(|obj| {
+    ///         obj.set_property("connection", "connected");
+    ///         Ok::<_, phper::Error>(())
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new_lazy_ghost<E: Throwable>(
+        &self, initializer: impl FnOnce(&mut ZObj) -> Result<(), E> + 'static,
+    ) -> crate::Result<ZObject> {
+        self.make_lazy(
+            false,
+            Box::new(move |obj, _| {
+                if let Err(e) = initializer(obj) {
+                    unsafe {
+                        throw(e);
+                    }
+                }
+            }),
+        )
+    }
+
+    /// Create the lazy proxy object of the class, like
+    /// `ReflectionClass::newLazyProxy()`.
+    ///
+    /// The initializer is called with the proxy object when the state of the
+    /// proxy is first accessed, and should return the real instance, which
+    /// the accesses of the proxy are forwarded to. Like
+    /// [ClassEntry::new_lazy_ghost], the initializer is called at most once.
+    ///
+    /// Only the user classes (and `stdClass`) can be lazy, the classes
+    /// registered by phper are internal classes, so the `Error` will be
+    /// returned.
+    pub fn new_lazy_proxy<E: Throwable>(
+        &self, initializer: impl FnOnce(&mut ZObj) -> Result<ZObject, E> + 'static,
+    ) -> crate::Result<ZObject> {
+        self.make_lazy(
+            true,
+            Box::new(move |obj, return_value| match initializer(obj) {
+                Ok(instance) => *return_value = instance.into(),
+                Err(e) => unsafe {
+                    throw(e);
+                },
+            }),
+        )
+    }
+
+    fn make_lazy(&self, proxy: bool, initializer: Initializer) -> crate::Result<ZObject> {
+        self.check_instantiable()?;
+
+        let mut ptr = null_mut();
+        call_raw_common(|_| unsafe {
+            ptr = phper_zend_object_make_lazy(self.as_ptr() as *mut _, proxy);
+        })?;
+        if ptr.is_null() {
+            return Err(InitializeObjectError::new(self.get_name().to_str()?.to_owned()).into());
+        }
+
+        // The object is owned by the returned `ZObject`.
+        let mut object = unsafe { ZObject::from_raw_cast(ptr) };
+        INITIALIZERS.with_borrow_mut(|initializers| {
+            initializers
+                .get_or_insert_with(ZWeakMap::new)
+                .insert(&mut object, initializer);
+        });
+        Ok(object)
+    }
+}
+
+impl ZObj {
+    /// Returns true if the object is the lazy object which isn't initialized
+    /// yet, like `ReflectionClass::isUninitializedLazyObject()`.
+    pub fn is_uninitialized_lazy_object(&self) -> bool {
+        unsafe { !phper_zend_lazy_object_initialized(self.as_ptr()) }
+    }
+
+    /// Initialize the lazy object, do nothing if the object isn't lazy or has
+    /// been initialized, like `ReflectionClass::initializeLazyObject()`.
+    ///
+    /// The exception thrown by the initializer is returned.
+    pub fn initialize_lazy_object(&mut self) -> crate::Result<()> {
+        call_raw_common(|_| unsafe {
+            phper_zend_lazy_object_init(self.as_mut_ptr());
+        })?;
+        Ok(())
+    }
+}
+
+/// Initialize the function wrapped by the initializer closures, called in
+/// `MINIT`.
+pub(crate) fn startup() {
+    unsafe {
+        phper_zend_lazy_objects_startup(Some(lazy_initializer));
+    }
+}
+
+/// Drop the initializers of the lazy objects which aren't initialized, called
+/// in `RSHUTDOWN`.
+pub(crate) fn reset() {
+    INITIALIZERS.take();
+}
+
+unsafe extern "C" fn lazy_initializer(
+    execute_data: *mut zend_execute_data, return_value: *mut zval,
+) {
+    unsafe {
+        let execute_data = ExecuteData::from_mut_ptr(execute_data);
+        let return_value = ZVal::from_mut_ptr(return_value);
+
+        let mut arguments = execute_data.get_parameters_array();
+        let Some(obj) = arguments.first_mut().and_then(|obj| obj.as_mut_z_obj()) else {
+            return;
+        };
+
+        // Release the borrow before calling the initializer, which may create
+        // other lazy objects.
+        let initializer = INITIALIZERS.with_borrow_mut(|initializers| {
+            initializers
+                .as_mut()
+                .and_then(|initializers| initializers.remove(obj))
+        });
+
+        match initializer {
+            Some(initializer) => initializer(obj, return_value),
+            None => throw(crate::Error::boxed(
+                "the initializer of the lazy object has been dropped",
+            )),
+        }
+    }
+}
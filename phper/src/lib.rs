@@ -18,22 +18,39 @@ mod macros;
 
 pub mod alloc;
 pub mod arrays;
+pub mod attributes;
+pub mod backtrace;
 pub mod classes;
-pub(crate) mod constants;
+pub mod constants;
 pub mod enums;
 pub mod errors;
+pub mod execute;
+pub mod fibers;
 pub mod functions;
+pub mod globals;
+pub mod info;
 pub mod ini;
+pub mod interrupts;
+mod lazy_objects;
 pub mod modules;
 pub mod objects;
 pub mod output;
 pub mod references;
 pub mod resources;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub mod runtime;
+pub mod sapi;
 pub mod strings;
 pub mod types;
 mod utils;
 pub mod values;
+pub mod weakrefs;
+pub mod zend_extensions;
 
-pub use crate::errors::{Error, Result, ok};
-pub use phper_macros::php_get_module;
+pub use crate::{
+    backtrace::backtrace,
+    errors::{Error, Result, ok},
+};
+pub use phper_macros::{PhpEnum, php_get_module, php_zend_extension};
 pub use phper_sys as sys;
@@ -11,13 +11,18 @@
 //! Apis relate to [zend_module_entry].
 
 use crate::{
-    classes::{ClassEntity, Interface, InterfaceEntity, StateClass},
+    classes::{ClassEntity, Interface, InterfaceEntity, StateClass, Trait, TraitEntity},
     constants::Constant,
     errors::Throwable,
-    functions::{Function, FunctionEntity, FunctionEntry, FunctionExecuteData, HandlerMap},
+    execute::{self, ExecuteEx, ExecuteHooks, ExecuteInternal},
+    functions::{
+        Function, FunctionEntity, FunctionEntry, FunctionExecuteData, FunctionReplacement,
+        HandlerMap, OriginalHandler, ReplacedFunctions, TraitHandlerMap,
+    },
+    info::InfoBuilder,
     ini,
     sys::*,
-    types::Scalar,
+    types::{self, Scalar},
     utils::ensure_end_with_zero,
     values::ZVal,
 };
@@ -59,6 +64,12 @@ type RequestHook = dyn Fn() + Send + Sync;
 #[cfg(not(phper_zts))]
 type RequestHook = dyn Fn();
 
+#[cfg(phper_zts)]
+type InfoHook = dyn Fn(&mut InfoBuilder) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+type InfoHook = dyn Fn(&mut InfoBuilder);
+
 unsafe extern "C" fn module_startup(_type: c_int, module_number: c_int) -> c_int {
     unsafe {
         let module = global_module_mut();
@@ -73,17 +84,29 @@ unsafe extern "C" fn module_startup(_type: c_int, module_number: c_int) -> c_int
             interface_entity.init();
         }
 
+        for trait_entity in &module.trait_entities {
+            let ce = trait_entity.init();
+            module.handler_map.extend(trait_entity.handler_map());
+            module
+                .trait_handler_map
+                .extend(trait_entity.trait_handler_map(ce));
+        }
+
         for function_entity in &module.function_entities {
             module.handler_map.insert(
                 (None, function_entity.name.clone()),
                 function_entity.handler.clone(),
             );
+            #[cfg(phper_major_version = "8")]
+            function_entity.register_attributes();
         }
 
         for class_entity in &module.class_entities {
             let ce = class_entity.init();
             class_entity.declare_properties(ce);
             module.handler_map.extend(class_entity.handler_map());
+            let trait_handlers = class_entity.use_traits(ce, &module.trait_handler_map);
+            module.handler_map.extend(trait_handlers);
         }
 
         #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
@@ -92,6 +115,17 @@ unsafe extern "C" fn module_startup(_type: c_int, module_number: c_int) -> c_int
             module.handler_map.extend(enum_entity.handler_map());
         }
 
+        module.execute_hooks.install();
+        module.replaced_functions.install();
+
+        #[cfg(phper_php_8_4_or_later)]
+        crate::lazy_objects::startup();
+
+        #[cfg(feature = "tokio")]
+        if let Some(builder) = take(&mut module.runtime_builder) {
+            crate::runtime::startup(builder);
+        }
+
         if let Some(f) = take(&mut module.module_init) {
             f();
         }
@@ -106,10 +140,22 @@ unsafe extern "C" fn module_shutdown(_type: c_int, module_number: c_int) -> c_in
 
         ini::unregister(module_number);
 
+        module.execute_hooks.uninstall();
+        module.replaced_functions.uninstall();
+
         if let Some(f) = take(&mut module.module_shutdown) {
             f();
         }
 
+        #[cfg(feature = "tokio")]
+        crate::runtime::shutdown();
+
+        for class_entity in &module.class_entities {
+            class_entity.shutdown();
+        }
+
+        types::free_persistent_arrays();
+
         ZEND_RESULT_CODE_SUCCESS
     }
 }
@@ -118,6 +164,8 @@ unsafe extern "C" fn request_startup(_type: c_int, _module_number: c_int) -> c_i
     unsafe {
         let module = global_module();
 
+        execute::reset();
+
         if let Some(f) = &module.request_init {
             f();
         }
@@ -134,6 +182,9 @@ unsafe extern "C" fn request_shutdown(_type: c_int, _module_number: c_int) -> c_
             f();
         }
 
+        #[cfg(phper_php_8_4_or_later)]
+        crate::lazy_objects::reset();
+
         ZEND_RESULT_CODE_SUCCESS
     }
 }
@@ -154,7 +205,15 @@ unsafe extern "C" fn module_info(zend_module: *mut zend_module_entry) {
         }
         php_info_print_table_end();
 
-        display_ini_entries(zend_module);
+        let mut info = InfoBuilder::default();
+        if let Some(f) = &module.info {
+            f(&mut info);
+        }
+        info.render();
+
+        if !info.is_ini_entries_hidden() {
+            display_ini_entries(zend_module);
+        }
     }
 }
 
@@ -171,13 +230,22 @@ pub struct Module {
     function_entities: Vec<FunctionEntity>,
     class_entities: Vec<ClassEntity<()>>,
     interface_entities: Vec<InterfaceEntity>,
+    trait_entities: Vec<TraitEntity>,
     #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
     enum_entities: Vec<crate::enums::EnumEntity<()>>,
     constants: Vec<Constant>,
     ini_entities: Vec<ini::IniEntity>,
     infos: HashMap<CString, CString>,
+    info: Option<Box<InfoHook>>,
+    dependencies: Vec<ModuleDependency>,
+    #[cfg(feature = "tokio")]
+    runtime_builder: Option<crate::runtime::RuntimeBuilder>,
+    pub(crate) execute_hooks: ExecuteHooks,
+    pub(crate) replaced_functions: ReplacedFunctions,
     /// Used to find the handler in the invoke function.
     pub(crate) handler_map: HandlerMap,
+    /// Used to find the handler of trait methods in the invoke function.
+    pub(crate) trait_handler_map: TraitHandlerMap,
 }
 
 impl Module {
@@ -196,12 +264,20 @@ impl Module {
             function_entities: vec![],
             class_entities: Default::default(),
             interface_entities: Default::default(),
+            trait_entities: Default::default(),
             #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
             enum_entities: Default::default(),
             constants: Default::default(),
             ini_entities: Default::default(),
             infos: Default::default(),
+            info: None,
+            dependencies: Default::default(),
+            #[cfg(feature = "tokio")]
+            runtime_builder: None,
+            execute_hooks: Default::default(),
+            replaced_functions: Default::default(),
             handler_map: Default::default(),
+            trait_handler_map: Default::default(),
         }
     }
 
@@ -215,6 +291,22 @@ impl Module {
         self.module_shutdown = Some(Box::new(func));
     }
 
+    /// Register the builder of the shared [tokio] runtime, used by
+    /// [runtime::block_on](crate::runtime::block_on), and shut down in
+    /// `MSHUTDOWN`.
+    ///
+    /// The runtime is built lazily on the first use in each process, so the
+    /// builder may be called more than once, such as in the forked workers of
+    /// php-fpm.
+    ///
+    /// Without it, the current thread runtime is built lazily for each thread.
+    #[cfg(feature = "tokio")]
+    pub fn enable_runtime(
+        &mut self, builder: impl Fn() -> tokio::runtime::Builder + Send + Sync + 'static,
+    ) {
+        self.runtime_builder = Some(Box::new(builder));
+    }
+
     /// Register `RINIT` hook.
     #[cfg(phper_zts)]
     pub fn on_request_init(&mut self, func: impl Fn() + Send + Sync + 'static) {
@@ -239,6 +331,52 @@ impl Module {
         self.request_shutdown = Some(Box::new(func));
     }
 
+    /// Register `zend_execute_ex` hook, called when a user function or
+    /// method is executed.
+    ///
+    /// The function is executed by [`ExecuteEx::call`], or after the hook
+    /// returns if it isn't called. Calls made by the hook itself, before or
+    /// after [`ExecuteEx::call`], won't trigger the hook again.
+    #[cfg(phper_zts)]
+    pub fn on_execute_ex(&mut self, func: impl Fn(ExecuteEx<'_>) + Send + Sync + 'static) {
+        self.execute_hooks.execute_ex = Some(Box::new(func));
+    }
+
+    /// Register `zend_execute_ex` hook, called when a user function or
+    /// method is executed.
+    ///
+    /// The function is executed by [`ExecuteEx::call`], or after the hook
+    /// returns if it isn't called. Calls made by the hook itself, before or
+    /// after [`ExecuteEx::call`], won't trigger the hook again.
+    #[cfg(not(phper_zts))]
+    pub fn on_execute_ex(&mut self, func: impl Fn(ExecuteEx<'_>) + 'static) {
+        self.execute_hooks.execute_ex = Some(Box::new(func));
+    }
+
+    /// Register `zend_execute_internal` hook, called when an internal function
+    /// or method is executed.
+    ///
+    /// The function is executed by [`ExecuteInternal::call`], or after the
+    /// hook returns if it isn't called. Calls made by the hook itself won't
+    /// trigger the hook again.
+    #[cfg(phper_zts)]
+    pub fn on_execute_internal(
+        &mut self, func: impl Fn(ExecuteInternal<'_>) + Send + Sync + 'static,
+    ) {
+        self.execute_hooks.execute_internal = Some(Box::new(func));
+    }
+
+    /// Register `zend_execute_internal` hook, called when an internal function
+    /// or method is executed.
+    ///
+    /// The function is executed by [`ExecuteInternal::call`], or after the
+    /// hook returns if it isn't called. Calls made by the hook itself won't
+    /// trigger the hook again.
+    #[cfg(not(phper_zts))]
+    pub fn on_execute_internal(&mut self, func: impl Fn(ExecuteInternal<'_>) + 'static) {
+        self.execute_hooks.execute_internal = Some(Box::new(func));
+    }
+
     /// Register function to module.
     pub fn add_function<F, Z, E>(
         &mut self, name: impl Into<String>, handler: F,
@@ -273,6 +411,73 @@ impl Module {
         self.function_entities.last_mut().unwrap()
     }
 
+    /// Replace the handler of an existing internal function, such as
+    /// `curl_exec`.
+    ///
+    /// The function is looked up in `MINIT`, so the extension defining it
+    /// must be loaded before this module. The hook receives the
+    /// [`OriginalHandler`], the original function is executed by
+    /// [`OriginalHandler::call`], or after the hook returns if it isn't
+    /// called. The original handler is restored in `MSHUTDOWN`.
+    #[cfg(phper_zts)]
+    pub fn replace_function(
+        &mut self, name: impl Into<String>,
+        handler: impl Fn(OriginalHandler<'_>) + Send + Sync + 'static,
+    ) {
+        self.replaced_functions
+            .push(FunctionReplacement::new(None, name, Box::new(handler)));
+    }
+
+    /// Replace the handler of an existing internal function, such as
+    /// `curl_exec`.
+    ///
+    /// The function is looked up in `MINIT`, so the extension defining it
+    /// must be loaded before this module. The hook receives the
+    /// [`OriginalHandler`], the original function is executed by
+    /// [`OriginalHandler::call`], or after the hook returns if it isn't
+    /// called. The original handler is restored in `MSHUTDOWN`.
+    #[cfg(not(phper_zts))]
+    pub fn replace_function(
+        &mut self, name: impl Into<String>, handler: impl Fn(OriginalHandler<'_>) + 'static,
+    ) {
+        self.replaced_functions
+            .push(FunctionReplacement::new(None, name, Box::new(handler)));
+    }
+
+    /// Replace the handler of an existing internal method, such as
+    /// `PDO::query`.
+    ///
+    /// Same as [`replace_function`](Module::replace_function), but looks up
+    /// the method in the function table of the class.
+    #[cfg(phper_zts)]
+    pub fn replace_method(
+        &mut self, class_name: impl Into<String>, method_name: impl Into<String>,
+        handler: impl Fn(OriginalHandler<'_>) + Send + Sync + 'static,
+    ) {
+        self.replaced_functions.push(FunctionReplacement::new(
+            Some(class_name.into()),
+            method_name,
+            Box::new(handler),
+        ));
+    }
+
+    /// Replace the handler of an existing internal method, such as
+    /// `PDO::query`.
+    ///
+    /// Same as [`replace_function`](Module::replace_function), but looks up
+    /// the method in the function table of the class.
+    #[cfg(not(phper_zts))]
+    pub fn replace_method(
+        &mut self, class_name: impl Into<String>, method_name: impl Into<String>,
+        handler: impl Fn(OriginalHandler<'_>) + 'static,
+    ) {
+        self.replaced_functions.push(FunctionReplacement::new(
+            Some(class_name.into()),
+            method_name,
+            Box::new(handler),
+        ));
+    }
+
     /// Register class to module.
     pub fn add_class<T>(&mut self, class: ClassEntity<T>) -> StateClass<T> {
         let bound_class = class.bound_class();
@@ -291,6 +496,13 @@ impl Module {
         bound_interface
     }
 
+    /// Register trait to module.
+    pub fn add_trait(&mut self, trait_entity: TraitEntity) -> Trait {
+        let bound_trait = trait_entity.bound_trait();
+        self.trait_entities.push(trait_entity);
+        bound_trait
+    }
+
     /// Register enum to module.
     #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
     pub fn add_enum<B: crate::enums::EnumBackingType>(
@@ -307,17 +519,61 @@ impl Module {
     }
 
     /// Register constant to module.
-    pub fn add_constant(&mut self, name: impl Into<String>, value: impl Into<Scalar>) {
+    pub fn add_constant(
+        &mut self, name: impl Into<String>, value: impl Into<Scalar>,
+    ) -> &mut Constant {
         self.constants.push(Constant::new(name, value));
+        self.constants.last_mut().unwrap()
     }
 
     /// Register ini configuration to module.
     pub fn add_ini(
         &mut self, name: impl Into<String>, default_value: impl ini::IntoIniValue,
         policy: ini::Policy,
-    ) {
+    ) -> &mut ini::IniEntity {
         self.ini_entities
             .push(ini::IniEntity::new(name, default_value, policy));
+        self.ini_entities.last_mut().unwrap()
+    }
+
+    /// Register ini configuration with parser to module.
+    ///
+    /// The parser is called when the entry is modified at startup or by
+    /// `ini_set`, the invalid value is rejected with a warning. The parsed
+    /// value can be read by the returned [`IniBinding`](ini::IniBinding)
+    /// without parsing again.
+    #[cfg(phper_zts)]
+    pub fn add_ini_with<T, E>(
+        &mut self, name: impl Into<String>, default_value: impl ini::IntoIniValue,
+        policy: ini::Policy, parse: impl Fn(&str) -> Result<T, E> + Send + Sync + 'static,
+    ) -> ini::IniBinding<T>
+    where
+        T: 'static,
+        E: std::fmt::Display,
+    {
+        let (entity, binding) = ini::IniEntity::new_with(name, default_value, policy, parse);
+        self.ini_entities.push(entity);
+        binding
+    }
+
+    /// Register ini configuration with parser to module.
+    ///
+    /// The parser is called when the entry is modified at startup or by
+    /// `ini_set`, the invalid value is rejected with a warning. The parsed
+    /// value can be read by the returned [`IniBinding`](ini::IniBinding)
+    /// without parsing again.
+    #[cfg(not(phper_zts))]
+    pub fn add_ini_with<T, E>(
+        &mut self, name: impl Into<String>, default_value: impl ini::IntoIniValue,
+        policy: ini::Policy, parse: impl Fn(&str) -> Result<T, E> + 'static,
+    ) -> ini::IniBinding<T>
+    where
+        T: 'static,
+        E: std::fmt::Display,
+    {
+        let (entity, binding) = ini::IniEntity::new_with(name, default_value, policy, parse);
+        self.ini_entities.push(entity);
+        binding
     }
 
     /// Register info item.
@@ -331,6 +587,50 @@ impl Module {
         self.infos.insert(key, value);
     }
 
+    /// Declare the module required by this module, the required module will
+    /// be started up before this module, and this module fails to load if
+    /// the required module is missing.
+    pub fn requires(&mut self, name: impl Into<String>) {
+        self.dependencies
+            .push(ModuleDependency::new(name, MODULE_DEP_REQUIRED));
+    }
+
+    /// Declare the module conflicting with this module, this module fails to
+    /// load if the conflicting module is loaded.
+    pub fn conflicts(&mut self, name: impl Into<String>) {
+        self.dependencies
+            .push(ModuleDependency::new(name, MODULE_DEP_CONFLICTS));
+    }
+
+    /// Declare the module optionally used by this module, the optional module
+    /// will be started up before this module if it is loaded.
+    pub fn optional(&mut self, name: impl Into<String>) {
+        self.dependencies
+            .push(ModuleDependency::new(name, MODULE_DEP_OPTIONAL));
+    }
+
+    /// Register `MINFO` hook, to add the extension information tables shown
+    /// by `phpinfo()` and `php --ri <EXTENSION_NAME>`, after the table of
+    /// version, authors and the items added by [`add_info`](Module::add_info).
+    ///
+    /// The hook is called every time the information is shown, so the runtime
+    /// values can be shown.
+    #[cfg(phper_zts)]
+    pub fn on_info(&mut self, func: impl Fn(&mut InfoBuilder) + Send + Sync + 'static) {
+        self.info = Some(Box::new(func));
+    }
+
+    /// Register `MINFO` hook, to add the extension information tables shown
+    /// by `phpinfo()` and `php --ri <EXTENSION_NAME>`, after the table of
+    /// version, authors and the items added by [`add_info`](Module::add_info).
+    ///
+    /// The hook is called every time the information is shown, so the runtime
+    /// values can be shown.
+    #[cfg(not(phper_zts))]
+    pub fn on_info(&mut self, func: impl Fn(&mut InfoBuilder) + 'static) {
+        self.info = Some(Box::new(func));
+    }
+
     /// Leak memory to generate `zend_module_entry` pointer.
     #[doc(hidden)]
     pub unsafe fn module_entry(self) -> *const zend_module_entry {
@@ -354,7 +654,7 @@ impl Module {
                 zend_debug: ZEND_DEBUG as c_uchar,
                 zts: USING_ZTS as c_uchar,
                 ini_entry: null(),
-                deps: null(),
+                deps: module.module_deps(),
                 name: module.name.as_ptr(),
                 functions: module.function_entries(),
                 module_startup_func: Some(module_startup),
@@ -406,6 +706,20 @@ impl Module {
         }
     }
 
+    fn module_deps(&self) -> *const zend_module_dep {
+        if self.dependencies.is_empty() {
+            return null();
+        }
+
+        let mut deps = Vec::new();
+        for dependency in &self.dependencies {
+            deps.push(dependency.entry());
+        }
+        deps.push(unsafe { zeroed::<zend_module_dep>() });
+
+        Box::into_raw(deps.into_boxed_slice()).cast()
+    }
+
     fn function_entries(&self) -> *const zend_function_entry {
         if self.function_entities.is_empty() {
             return null();
@@ -425,3 +739,28 @@ impl Module {
         &self.class_entities
     }
 }
+
+/// The dependency of module, see [`Module::requires`], [`Module::conflicts`]
+/// and [`Module::optional`].
+struct ModuleDependency {
+    name: CString,
+    dep_type: u32,
+}
+
+impl ModuleDependency {
+    fn new(name: impl Into<String>, dep_type: u32) -> Self {
+        Self {
+            name: ensure_end_with_zero(name),
+            dep_type,
+        }
+    }
+
+    fn entry(&self) -> zend_module_dep {
+        zend_module_dep {
+            name: self.name.as_ptr(),
+            rel: null(),
+            version: null(),
+            type_: self.dep_type as c_uchar,
+        }
+    }
+}
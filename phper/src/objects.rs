@@ -12,7 +12,8 @@
 
 use crate::{
     alloc::EBox,
-    classes::ClassEntry,
+    arrays::{Iter, IterKey, ZArr},
+    classes::{ClassEntry, Visibility},
     functions::{ZFunc, call_internal, call_raw_common},
     sys::*,
     values::ZVal,
@@ -20,6 +21,7 @@ use crate::{
 use phper_alloc::{RefClone, ToRefOwned};
 use std::{
     any::Any,
+    borrow::Cow,
     ffi::c_void,
     fmt::{self, Debug},
     marker::PhantomData,
@@ -95,6 +97,18 @@ impl ZObj {
         &mut self.inner
     }
 
+    /// Creates the owned object by incrementing the refcount, same as
+    /// [ToRefOwned::to_ref_owned], but only needs the shared reference, since
+    /// nothing but the refcount managed by PHP is changed.
+    pub fn new_ref(&self) -> ZObject {
+        let mut val = ManuallyDrop::new(ZVal::default());
+        unsafe {
+            phper_zval_obj(val.as_mut_ptr(), self.as_ptr() as *mut _);
+            phper_z_addref_p(val.as_mut_ptr());
+            ZObject::from_raw_cast(val.as_mut_z_obj().unwrap().as_mut_ptr())
+        }
+    }
+
     /// Upgrade to state obj.
     ///
     /// # Safety
@@ -145,9 +159,36 @@ impl ZObj {
         unsafe { ZVal::from_mut_ptr(prop) }
     }
 
-    #[allow(clippy::useless_conversion)]
+    /// Get the copy of property by name of object in the scope of the given
+    /// class, so the private and protected properties declared by the class
+    /// are accessible, such as the private properties of the parent class.
+    ///
+    /// The `__get` magic method is called for inaccessible properties, so the
+    /// value is returned by copy rather than reference.
+    pub fn get_scoped_property(&self, scope: &ClassEntry, name: impl AsRef<str>) -> ZVal {
+        let object = self.as_ptr() as *mut _;
+        let mut rv = ZVal::default();
+        let prop = Self::read_property(scope.as_ptr() as *mut _, object, name, rv.as_mut_ptr());
+        if prop == rv.as_mut_ptr() {
+            return rv;
+        }
+        let prop = unsafe { ZVal::from_ptr(prop) };
+        match prop.as_z_ref() {
+            Some(reference) => reference.val().clone(),
+            None => prop.clone(),
+        }
+    }
+
     fn inner_get_property(
         scope: *mut zend_class_entry, object: *mut zend_object, name: impl AsRef<str>,
+    ) -> *mut zval {
+        Self::read_property(scope, object, name, null_mut())
+    }
+
+    #[allow(clippy::useless_conversion)]
+    fn read_property(
+        scope: *mut zend_class_entry, object: *mut zend_object, name: impl AsRef<str>,
+        rv: *mut zval,
     ) -> *mut zval {
         let name = name.as_ref();
 
@@ -160,7 +201,7 @@ impl ZObj {
                     name.as_ptr().cast(),
                     name.len().try_into().unwrap(),
                     true.into(),
-                    null_mut(),
+                    rv,
                 )
             }
             #[cfg(phper_major_version = "7")]
@@ -173,7 +214,7 @@ impl ZObj {
                     name.as_ptr().cast(),
                     name.len().try_into().unwrap(),
                     true.into(),
-                    null_mut(),
+                    rv,
                 )
             }
         }
@@ -210,6 +251,89 @@ impl ZObj {
         }
     }
 
+    /// Check whether the property exists and isn't null, like `isset()`, or
+    /// isn't empty like `!empty()` if `check_empty` is true.
+    ///
+    /// The `__isset` magic method is called for inaccessible properties.
+    pub fn has_property(&self, name: impl AsRef<str>, check_empty: bool) -> bool {
+        self.inner_has_property(self.inner.ce, name, check_empty)
+    }
+
+    /// Same as [ZObj::has_property], but in the scope of the given class, see
+    /// [ZObj::get_scoped_property].
+    pub fn has_scoped_property(
+        &self, scope: &ClassEntry, name: impl AsRef<str>, check_empty: bool,
+    ) -> bool {
+        self.inner_has_property(scope.as_ptr() as *mut _, name, check_empty)
+    }
+
+    fn inner_has_property(
+        &self, scope: *mut zend_class_entry, name: impl AsRef<str>, check_empty: bool,
+    ) -> bool {
+        let name = name.as_ref();
+        unsafe {
+            phper_zend_object_has_property(
+                self.as_ptr() as *mut _,
+                scope,
+                name.as_ptr().cast(),
+                name.len(),
+                check_empty,
+            )
+        }
+    }
+
+    /// Unset the property by name of object, like `unset()`.
+    ///
+    /// The `__unset` magic method is called for inaccessible properties.
+    pub fn unset_property(&mut self, name: impl AsRef<str>) {
+        let scope = self.inner.ce;
+        self.inner_unset_property(scope, name)
+    }
+
+    /// Same as [ZObj::unset_property], but in the scope of the given class, see
+    /// [ZObj::get_scoped_property].
+    pub fn unset_scoped_property(&mut self, scope: &ClassEntry, name: impl AsRef<str>) {
+        self.inner_unset_property(scope.as_ptr() as *mut _, name)
+    }
+
+    fn inner_unset_property(&mut self, scope: *mut zend_class_entry, name: impl AsRef<str>) {
+        let name = name.as_ref();
+        unsafe {
+            phper_zend_object_unset_property(
+                self.as_mut_ptr(),
+                scope,
+                name.as_ptr().cast(),
+                name.len(),
+            )
+        }
+    }
+
+    /// Gets the properties of object like `(array) $obj`, including the
+    /// declared and dynamic properties, the uninitialized or unset properties
+    /// are skipped when iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use phper::{classes::Visibility, objects::ZObj};
+    ///
+    /// fn public_property_names(obj: &ZObj) -> Vec<String> {
+    ///     obj.properties()
+    ///         .iter()
+    ///         .filter(|(_, _, vis)| *vis == Visibility::Public)
+    ///         .map(|(name, ..)| name.into_owned())
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn properties(&self) -> Properties<'_> {
+        unsafe {
+            Properties {
+                table: phper_zend_object_get_properties_for(self.as_ptr() as *mut _),
+                _p: PhantomData,
+            }
+        }
+    }
+
     /// Call the object method by name.
     ///
     /// # Examples
@@ -277,13 +401,9 @@ impl Drop for ZObj {
 impl ToRefOwned for ZObj {
     type Owned = ZObject;
 
+    #[inline]
     fn to_ref_owned(&mut self) -> Self::Owned {
-        let mut val = ManuallyDrop::new(ZVal::default());
-        unsafe {
-            phper_zval_obj(val.as_mut_ptr(), self.as_mut_ptr());
-            phper_z_addref_p(val.as_mut_ptr());
-            ZObject::from_raw_cast(val.as_mut_z_obj().unwrap().as_mut_ptr())
-        }
+        self.new_ref()
     }
 }
 
@@ -293,6 +413,88 @@ impl Debug for ZObj {
     }
 }
 
+/// The properties table of [ZObj], released when dropped.
+///
+/// This is created by [`properties`].
+///
+/// [`properties`]: ZObj::properties
+pub struct Properties<'a> {
+    table: *mut zend_array,
+    _p: PhantomData<&'a ZObj>,
+}
+
+impl Properties<'_> {
+    /// Provides an iterator over the properties.
+    pub fn iter(&self) -> PropertiesIter<'_> {
+        PropertiesIter {
+            inner: unsafe { ZArr::try_from_ptr(self.table) }.map(ZArr::iter),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Properties<'_> {
+    type IntoIter = PropertiesIter<'a>;
+    type Item = (Cow<'a, str>, &'a ZVal, Visibility);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Drop for Properties<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            phper_zend_object_release_properties(self.table);
+        }
+    }
+}
+
+/// An iterator over the properties of [ZObj], yields the unmangled name, the
+/// value and the visibility of properties.
+///
+/// This is created by [`Properties::iter`].
+pub struct PropertiesIter<'a> {
+    inner: Option<Iter<'a>>,
+}
+
+impl<'a> Iterator for PropertiesIter<'a> {
+    type Item = (Cow<'a, str>, &'a ZVal, Visibility);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (key, mut value) = self.inner.as_mut()?.next()?;
+
+            // The declared properties are stored as indirect slots.
+            if value.get_type_info().get_base_type().into_raw() == IS_INDIRECT {
+                value = unsafe { ZVal::from_ptr(phper_z_indirect_p(value.as_ptr())) };
+            }
+            if value.get_type_info().is_undef() {
+                continue;
+            }
+
+            let (name, vis) = match key {
+                IterKey::Index(index) => (Cow::Owned(index.to_string()), Visibility::Public),
+                IterKey::ZStr(key) => unmangle_property_name(key.to_bytes()),
+            };
+            return Some((name, value, vis));
+        }
+    }
+}
+
+/// Unmangle the property name, the private property name is mangled as
+/// `\0Class\0name`, and the protected is `\0*\0name`.
+fn unmangle_property_name(key: &[u8]) -> (Cow<'_, str>, Visibility) {
+    let mangled = key.strip_prefix(b"\0").and_then(|key| {
+        let pos = key.iter().position(|b| *b == 0)?;
+        Some((&key[..pos], &key[pos + 1..]))
+    });
+    match mangled {
+        Some((b"*", name)) => (String::from_utf8_lossy(name), Visibility::Protected),
+        Some((_, name)) => (String::from_utf8_lossy(name), Visibility::Private),
+        None => (String::from_utf8_lossy(key), Visibility::Public),
+    }
+}
+
 /// An owned PHP object value.
 ///
 /// `ZObject` represents an owned PHP object allocated in the Zend Engine
@@ -0,0 +1,235 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the [tokio] runtime, enabled by the `tokio` feature.
+//!
+//! If the builder is registered by
+//! [Module::enable_runtime](crate::modules::Module::enable_runtime), the
+//! runtime is built lazily on the first use and shared by all threads of the
+//! process, otherwise the current thread runtime is built lazily for each
+//! thread. The runtimes are shut down in `MSHUTDOWN`.
+//!
+//! The runtimes aren't built in `MINIT`, because the master process of
+//! php-fpm or Apache prefork forks the workers after `MINIT`, and the threads
+//! of runtime don't survive the fork. The runtime inherited from the parent
+//! process is abandoned and built again in the child process.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use phper::{modules::Module, php_get_module, runtime};
+//! use std::time::Duration;
+//!
+//! #[php_get_module]
+//! pub fn get_module() -> Module {
+//!     let mut module = Module::new("hello", "0.1.0", "");
+//!
+//!     module.enable_runtime(tokio::runtime::Builder::new_multi_thread);
+//!
+//!     module.add_function("sleep_then_say", |_| {
+//!         runtime::block_on(async {
+//!             tokio::time::sleep(Duration::from_millis(100)).await;
+//!             "hello"
+//!         })
+//!     });
+//!
+//!     module
+//! }
+//! ```
+
+use crate::{
+    interrupts,
+    output::{LogLevel, log},
+};
+use std::{
+    cell::RefCell,
+    future::{Future, poll_fn},
+    io,
+    mem::forget,
+    pin::pin,
+    process,
+    rc::Rc,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    task::Poll,
+    time::Duration,
+};
+use tokio::{
+    runtime::{Builder, Handle, Runtime},
+    time::interval,
+};
+
+/// The interval of checking the PHP interrupts in [block_on].
+const INTERRUPT_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The builder of the shared runtime, registered by
+/// [Module::enable_runtime](crate::modules::Module::enable_runtime).
+pub(crate) type RuntimeBuilder = Box<dyn Fn() -> Builder + Send + Sync>;
+
+static SHARED_BUILDER: RwLock<Option<RuntimeBuilder>> = RwLock::new(None);
+
+static SHARED_RUNTIME: Mutex<Option<ProcessRuntime<Arc<Runtime>>>> = Mutex::new(None);
+
+thread_local! {
+    static LOCAL_RUNTIME: RefCell<Option<ProcessRuntime<Rc<Runtime>>>> = const { RefCell::new(None) };
+}
+
+/// The runtime with the id of the process which built it.
+struct ProcessRuntime<R> {
+    pid: u32,
+    runtime: R,
+}
+
+impl<R: Clone> ProcessRuntime<R> {
+    /// Gets the runtime built by the current process, or builds the new one.
+    ///
+    /// The runtime inherited from the parent process by `fork()` is leaked
+    /// rather than dropped, because its threads don't exist in the current
+    /// process, and dropping it may wait for them forever.
+    fn get_or_build(
+        slot: &mut Option<Self>, build: impl FnOnce() -> io::Result<R>,
+    ) -> io::Result<R> {
+        let pid = process::id();
+        match slot.take() {
+            Some(current) if current.pid == pid => {
+                let runtime = current.runtime.clone();
+                *slot = Some(current);
+                return Ok(runtime);
+            }
+            Some(inherited) => forget(inherited),
+            None => {}
+        }
+        let runtime = build()?;
+        *slot = Some(Self {
+            pid,
+            runtime: runtime.clone(),
+        });
+        Ok(runtime)
+    }
+
+    fn release(slot: Option<Self>) {
+        if let Some(inherited) = slot.filter(|current| current.pid != process::id()) {
+            forget(inherited);
+        }
+    }
+}
+
+/// Register the builder of the shared runtime, called in `MINIT`.
+pub(crate) fn startup(builder: RuntimeBuilder) {
+    *SHARED_BUILDER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(builder);
+}
+
+/// Shut down the shared runtime and the runtime of current thread, called in
+/// `MSHUTDOWN`.
+pub(crate) fn shutdown() {
+    ProcessRuntime::release(
+        SHARED_RUNTIME
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take(),
+    );
+    ProcessRuntime::release(LOCAL_RUNTIME.take());
+    SHARED_BUILDER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+}
+
+enum RuntimeRef {
+    Shared(Arc<Runtime>),
+    Local(Rc<Runtime>),
+}
+
+impl RuntimeRef {
+    fn get() -> io::Result<Self> {
+        let builder = SHARED_BUILDER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(builder) = &*builder {
+            let mut shared = SHARED_RUNTIME
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            return ProcessRuntime::get_or_build(&mut shared, || {
+                Ok(Arc::new(builder().enable_all().build()?))
+            })
+            .map(Self::Shared)
+            .inspect_err(|err| {
+                log(
+                    LogLevel::Error,
+                    format!("failed to build the shared runtime: {err}"),
+                )
+            });
+        }
+
+        LOCAL_RUNTIME.with_borrow_mut(|local| {
+            ProcessRuntime::get_or_build(local, || {
+                Ok(Rc::new(Builder::new_current_thread().enable_all().build()?))
+            })
+            .map(Self::Local)
+        })
+    }
+
+    fn as_runtime(&self) -> &Runtime {
+        match self {
+            Self::Shared(runtime) => runtime,
+            Self::Local(runtime) => runtime,
+        }
+    }
+}
+
+/// Get the handle of the runtime, used to spawn the tasks.
+///
+/// The tasks spawned on the shared runtime run on the worker threads, which
+/// aren't PHP threads, so they must not touch the PHP apis, such as [ZVal],
+/// the objects and the output functions, pass the plain Rust values to them
+/// and back instead.
+///
+/// The tasks spawned on the lazily built current thread runtime only make
+/// progress in [block_on].
+///
+/// [ZVal]: crate::values::ZVal
+pub fn handle() -> crate::Result<Handle> {
+    Ok(RuntimeRef::get()?.as_runtime().handle().clone())
+}
+
+/// Run the future to completion on the runtime, blocking the current thread.
+///
+/// The PHP interrupts are checked periodically by [interrupts::check] while
+/// blocking, if failed, the future is dropped and the error is returned.
+///
+/// Can't be called inside the async context, such as in the future passed to
+/// `block_on`.
+pub fn block_on<F: Future>(future: F) -> crate::Result<F::Output> {
+    if Handle::try_current().is_ok() {
+        return Err(crate::Error::boxed(
+            "Cannot block on the future inside the async context",
+        ));
+    }
+
+    let runtime = RuntimeRef::get()?;
+    runtime.as_runtime().block_on(async {
+        let mut future = pin!(future);
+        let mut interval = interval(INTERRUPT_CHECK_INTERVAL);
+
+        poll_fn(|cx| {
+            if let Err(e) = interrupts::check() {
+                return Poll::Ready(Err(e));
+            }
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+            // Wake up periodically to check the interrupts.
+            while interval.poll_tick(cx).is_ready() {}
+            Poll::Pending
+        })
+        .await
+    })
+}
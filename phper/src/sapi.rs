@@ -0,0 +1,132 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to [sapi_module_struct] and [sapi_globals_struct], like the
+//! request information and the response headers.
+
+use crate::{errors::SapiHeaderError, strings::ZString, sys::*, utils::ensure_end_with_zero};
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_void},
+};
+
+unsafe fn to_string(ptr: *const c_char) -> Option<String> {
+    unsafe { (!ptr.is_null()).then(|| CStr::from_ptr(ptr).to_string_lossy().into_owned()) }
+}
+
+/// Get the name of current SAPI, like `cli` and `fpm-fcgi`, same as PHP
+/// `php_sapi_name()`.
+pub fn name() -> String {
+    unsafe { to_string(sapi_module.name).unwrap_or_default() }
+}
+
+/// Get the request method, like `GET`, none for CLI.
+pub fn request_method() -> Option<String> {
+    unsafe { to_string(crate::sg!(request_info).request_method) }
+}
+
+/// Get the request uri, none for CLI.
+pub fn request_uri() -> Option<String> {
+    unsafe { to_string(crate::sg!(request_info).request_uri) }
+}
+
+/// Get the query string of request uri.
+pub fn query_string() -> Option<String> {
+    unsafe { to_string(crate::sg!(request_info).query_string) }
+}
+
+/// Get the content type of request.
+pub fn content_type() -> Option<String> {
+    unsafe { to_string(crate::sg!(request_info).content_type) }
+}
+
+/// Get the content length of request.
+pub fn content_length() -> i64 {
+    unsafe { crate::sg!(request_info).content_length }
+}
+
+/// Read the raw request body, same as reading `php://input`.
+pub fn read_request_body() -> ZString {
+    unsafe {
+        let body = phper_sapi_read_request_body();
+        if body.is_null() {
+            ZString::new("")
+        } else {
+            ZString::from_raw_cast(body)
+        }
+    }
+}
+
+/// Detect if the response headers are already sent, same as PHP
+/// `headers_sent()`.
+pub fn headers_sent() -> bool {
+    // `zend_bool` is `unsigned char` in PHP 7 and `bool` in PHP 8.
+    unsafe { crate::sg!(headers_sent) as u8 != 0 }
+}
+
+/// Detect if the SAPI doesn't send the response headers at all, namely
+/// `SG(request_info).no_headers`.
+pub fn no_headers() -> bool {
+    unsafe { crate::sg!(request_info).no_headers as u8 != 0 }
+}
+
+/// Add the response header, same as PHP `header()`.
+///
+/// If `replace` is true, the previous header with the same name will be
+/// replaced.
+pub fn add_header(header: &str, replace: bool) -> crate::Result<()> {
+    let op = if replace {
+        sapi_header_op_enum_SAPI_HEADER_REPLACE
+    } else {
+        sapi_header_op_enum_SAPI_HEADER_ADD
+    };
+    header_op(header, op)
+}
+
+/// Remove the response header by name, same as PHP `header_remove()`.
+pub fn remove_header(name: &str) -> crate::Result<()> {
+    header_op(name, sapi_header_op_enum_SAPI_HEADER_DELETE)
+}
+
+fn header_op(header: &str, op: sapi_header_op_enum) -> crate::Result<()> {
+    let line = ensure_end_with_zero(header);
+    unsafe {
+        let mut ctr = sapi_header_line::default();
+        ctr.line = line.as_ptr().cast_mut();
+        ctr.line_len = line.as_bytes().len();
+        if sapi_header_op(op, &mut ctr as *mut sapi_header_line as *mut c_void)
+            == ZEND_RESULT_CODE_SUCCESS
+        {
+            Ok(())
+        } else {
+            Err(SapiHeaderError::new(header.to_owned()).into())
+        }
+    }
+}
+
+/// Get the response status code, zero if not set.
+pub fn response_code() -> i32 {
+    unsafe { crate::sg!(sapi_headers).http_response_code }
+}
+
+/// Set the response status code, same as PHP `http_response_code()`.
+pub fn set_response_code(code: i32) -> crate::Result<()> {
+    unsafe {
+        if sapi_header_op(
+            sapi_header_op_enum_SAPI_HEADER_SET_STATUS,
+            code as isize as *mut c_void,
+        ) == ZEND_RESULT_CODE_SUCCESS
+        {
+            Ok(())
+        } else {
+            Err(SapiHeaderError::new(format!("HTTP/1.1 {}", code)).into())
+        }
+    }
+}
@@ -10,12 +10,15 @@
 
 //! Apis relate to PHP types.
 
-use crate::sys::*;
+use crate::{sys::*, values::ZVal};
 use derive_more::From;
 use std::{
     ffi::CStr,
     fmt::{self, Debug, Display},
+    mem::zeroed,
     os::raw::c_int,
+    ptr::NonNull,
+    sync::Mutex,
 };
 
 /// Wrapper of PHP type.
@@ -184,7 +187,11 @@ const fn get_base_type_by_raw(t: u32) -> u32 {
 }
 
 /// Copyable value, used in constant and class property.
+///
+/// It's non exhaustive, so more kinds of value can be supported later without
+/// breaking the matches.
 #[derive(From)]
+#[non_exhaustive]
 pub enum Scalar {
     /// Null.
     Null,
@@ -198,6 +205,8 @@ pub enum Scalar {
     String(String),
     /// Binary string.
     Bytes(Vec<u8>),
+    /// Array, can be nested, the items keep the insertion order.
+    Array(Vec<(ScalarKey, Scalar)>),
 }
 
 impl From<()> for Scalar {
@@ -218,6 +227,134 @@ impl From<&[u8]> for Scalar {
     }
 }
 
+impl<K: Into<ScalarKey>, V: Into<Scalar>> FromIterator<(K, V)> for Scalar {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::Array(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl Scalar {
+    /// Create the list array, the keys are `0, 1, 2...`.
+    pub fn list<V: Into<Scalar>>(items: impl IntoIterator<Item = V>) -> Self {
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (i as i64, v))
+            .collect()
+    }
+
+    /// Create the persistent zval, used for registering the constants and the
+    /// default property values in `MINIT`.
+    ///
+    /// The strings are interned, and the arrays are immutable, which are freed
+    /// by [free_persistent_arrays] in `MSHUTDOWN`.
+    pub(crate) unsafe fn to_persistent_zval(&self) -> zval {
+        unsafe {
+            match self {
+                Self::Null => ZVal::from(()).into_inner(),
+                Self::Bool(b) => ZVal::from(*b).into_inner(),
+                Self::I64(i) => ZVal::from(*i).into_inner(),
+                Self::F64(f) => ZVal::from(*f).into_inner(),
+                Self::String(s) => interned_zval(s.as_bytes()),
+                Self::Bytes(b) => interned_zval(b),
+                Self::Array(items) => {
+                    let ht = new_persistent_array(items);
+                    PERSISTENT_ARRAYS
+                        .lock()
+                        .unwrap()
+                        .push(PersistentArray(NonNull::new(ht).unwrap()));
+                    let mut val: zval = zeroed();
+                    phper_zval_immutable_arr(&mut val, ht);
+                    val
+                }
+            }
+        }
+    }
+}
+
+/// Key of the [Scalar::Array].
+#[derive(From)]
+pub enum ScalarKey {
+    /// Integer key.
+    Index(i64),
+    /// String key, the numeric string will be converted to integer key, same
+    /// as PHP.
+    Str(String),
+}
+
+impl From<&str> for ScalarKey {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_owned())
+    }
+}
+
+struct PersistentArray(NonNull<zend_array>);
+
+// The arrays are only created and freed in `MINIT` and `MSHUTDOWN`.
+unsafe impl Send for PersistentArray {}
+
+/// The arrays of module constants, class constants and default property values
+/// are owned by the process, rather than the constants or the classes, because
+/// the engine destroys their values by `zval_internal_ptr_dtor`, which only
+/// releases the strings and raises the fatal error for the arrays.
+///
+/// So the arrays are immutable, which the engine skips when destroying, and
+/// are kept here until `MSHUTDOWN`, after which no script can read them.
+static PERSISTENT_ARRAYS: Mutex<Vec<PersistentArray>> = Mutex::new(Vec::new());
+
+unsafe fn interned_zval(s: &[u8]) -> zval {
+    unsafe {
+        let mut val: zval = zeroed();
+        phper_zval_interned_stringl(&mut val, s.as_ptr().cast(), s.len());
+        val
+    }
+}
+
+unsafe fn new_persistent_array(items: &[(ScalarKey, Scalar)]) -> *mut zend_array {
+    unsafe {
+        let ht = phper_zend_new_persistent_array(items.len().try_into().unwrap());
+        for (key, value) in items {
+            let mut val = match value {
+                // Nested arrays are freed along with the outer one.
+                Scalar::Array(items) => {
+                    let mut val: zval = zeroed();
+                    phper_zval_immutable_arr(&mut val, new_persistent_array(items));
+                    val
+                }
+                value => value.to_persistent_zval(),
+            };
+            match key {
+                ScalarKey::Index(i) => {
+                    phper_zend_hash_index_update(ht, *i as zend_ulong, &mut val);
+                }
+                ScalarKey::Str(s) => {
+                    phper_zend_persistent_array_str_update(
+                        ht,
+                        s.as_ptr().cast(),
+                        s.len(),
+                        &mut val,
+                    );
+                }
+            }
+        }
+        ht
+    }
+}
+
+/// Free the arrays created by [Scalar::to_persistent_zval], called in
+/// `MSHUTDOWN`.
+pub(crate) unsafe fn free_persistent_arrays() {
+    for array in PERSISTENT_ARRAYS.lock().unwrap().drain(..) {
+        unsafe {
+            phper_zend_persistent_array_free(array.0.as_ptr());
+        }
+    }
+}
+
 /// PHP argument typehints
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ArgumentTypeHint {
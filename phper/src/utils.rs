@@ -15,3 +15,20 @@ use std::ffi::CString;
 pub(crate) fn ensure_end_with_zero(s: impl Into<String>) -> CString {
     CString::new(s.into()).expect("CString::new failed")
 }
+
+/// Escape the html special characters, like `htmlspecialchars` with
+/// `ENT_QUOTES`.
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#039;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -126,6 +126,17 @@ impl ExecuteData {
         unsafe { phper_zend_num_args(self.as_ptr()).try_into().unwrap() }
     }
 
+    /// Gets the previous execute data, which is the caller frame, if exists.
+    pub fn prev(&self) -> Option<&ExecuteData> {
+        unsafe { ExecuteData::try_from_ptr(self.inner.prev_execute_data) }
+    }
+
+    /// Gets the mutable previous execute data, which is the caller frame, if
+    /// exists.
+    pub fn prev_mut(&mut self) -> Option<&mut ExecuteData> {
+        unsafe { ExecuteData::try_from_mut_ptr(self.inner.prev_execute_data) }
+    }
+
     /// Gets associated function.
     pub fn func(&self) -> &ZFunc {
         unsafe { ZFunc::from_mut_ptr(self.inner.func) }
@@ -0,0 +1,277 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to the weak references of objects, only supported since PHP
+//! 7.4.
+//!
+//! [ZWeakRef] is built on the PHP `WeakReference`, and [ZWeakMap] (since PHP
+//! 8.1) is built on the weak reference hash table used by the PHP `WeakMap`.
+#![cfg(not(all(
+    phper_major_version = "7",
+    any(
+        phper_minor_version = "0",
+        phper_minor_version = "1",
+        phper_minor_version = "2",
+        phper_minor_version = "3",
+    )
+)))]
+
+use crate::{
+    alloc::ToRefOwned,
+    objects::{ZObj, ZObject},
+    sys::*,
+    values::ZVal,
+};
+use std::fmt::{self, Debug};
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    mem::{replace, zeroed},
+};
+
+/// Weak reference of object, which doesn't keep the object alive, wrapper of
+/// the PHP `WeakReference`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{objects::ZObject, weakrefs::ZWeakRef};
+///
+/// let mut obj = ZObject::new_by_std_class();
+/// let weak = ZWeakRef::new(&mut obj);
+/// assert!(weak.upgrade().is_some());
+///
+/// drop(obj);
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub struct ZWeakRef {
+    inner: ZObject,
+}
+
+impl ZWeakRef {
+    /// Create the weak reference of the object, like
+    /// `WeakReference::create($obj)`.
+    pub fn new(obj: &mut ZObj) -> Self {
+        let mut val = ZVal::default();
+        unsafe {
+            phper_zend_weakref_create(obj.as_mut_ptr(), val.as_mut_ptr());
+        }
+        let inner = val
+            .as_mut_z_obj()
+            .expect("WeakReference::create should return object")
+            .to_ref_owned();
+        Self { inner }
+    }
+
+    /// Get the strong reference of the object, return `None` if the object
+    /// has been destroyed.
+    pub fn upgrade(&self) -> Option<ZObject> {
+        let mut val = ZVal::default();
+        unsafe {
+            phper_zend_weakref_get(self.inner.as_ptr() as *mut _, val.as_mut_ptr());
+        }
+        val.as_mut_z_obj().map(ToRefOwned::to_ref_owned)
+    }
+
+    /// Gets the PHP `WeakReference` object.
+    #[inline]
+    pub fn as_z_obj(&self) -> &ZObj {
+        &self.inner
+    }
+}
+
+impl Clone for ZWeakRef {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.new_ref(),
+        }
+    }
+}
+
+impl Debug for ZWeakRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZWeakRef")
+            .field("alive", &self.upgrade().is_some())
+            .finish()
+    }
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+type WeakMapValues<V> = RefCell<HashMap<usize, Box<V>>>;
+
+/// Map keyed by objects without keeping them alive, the entry is removed when
+/// the object is destroyed, like the PHP `WeakMap` (>= PHP 8.1).
+///
+/// The map is bound to the current request, so it mustn't outlive the
+/// request, such as being stored in the module globals.
+///
+/// # Examples
+///
+/// ```no_run
+/// use phper::{objects::ZObject, weakrefs::ZWeakMap};
+///
+/// let mut map = ZWeakMap::new();
+/// let mut obj = ZObject::new_by_std_class();
+/// map.insert(&mut obj, "metadata");
+/// assert_eq!(map.get(&obj), Some(&"metadata"));
+///
+/// drop(obj);
+/// assert!(map.is_empty());
+/// ```
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+pub struct ZWeakMap<V> {
+    inner: Box<WeakMapInner<V>>,
+    _p: PhantomData<*mut ()>,
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+struct WeakMapInner<V> {
+    table: HashTable,
+    values: WeakMapValues<V>,
+}
+
+/// The entry stored in the weak reference hash table, used to remove the value
+/// when the object is destroyed.
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+struct WeakMapEntry<V> {
+    values: *const WeakMapValues<V>,
+    key: usize,
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+impl<V> ZWeakMap<V> {
+    /// Creates an empty `ZWeakMap`.
+    pub fn new() -> Self {
+        let mut inner = Box::new(WeakMapInner {
+            table: unsafe { zeroed() },
+            values: Default::default(),
+        });
+        unsafe {
+            phper_zend_weakrefs_hash_init(&mut inner.table, Some(weak_map_entry_dtor::<V>));
+        }
+        Self {
+            inner,
+            _p: PhantomData,
+        }
+    }
+
+    /// Inserts the value keyed by the object, returns the old value if the
+    /// object is already present.
+    pub fn insert(&mut self, obj: &mut ZObj, value: V) -> Option<V> {
+        let key = obj.as_ptr() as usize;
+
+        if let Some(old) = self.inner.values.borrow_mut().get_mut(&key) {
+            return Some(replace(&mut **old, value));
+        }
+
+        self.inner.values.borrow_mut().insert(key, Box::new(value));
+        let entry = Box::into_raw(Box::new(WeakMapEntry {
+            values: &self.inner.values as *const _,
+            key,
+        }));
+        unsafe {
+            if !phper_zend_weakrefs_hash_add_ptr(
+                &mut self.inner.table,
+                obj.as_mut_ptr(),
+                entry.cast(),
+            ) {
+                drop(Box::from_raw(entry));
+            }
+        }
+        None
+    }
+
+    /// Gets the reference of value keyed by the object.
+    pub fn get<'a>(&'a self, obj: &'a ZObj) -> Option<&'a V> {
+        let key = obj.as_ptr() as usize;
+        let values = self.inner.values.borrow();
+        // The boxed value won't be moved, and only be dropped when the object
+        // is destroyed or removed by `&mut self`.
+        values
+            .get(&key)
+            .map(|value| unsafe { &*(&**value as *const V) })
+    }
+
+    /// Gets the mutable reference of value keyed by the object.
+    pub fn get_mut<'a>(&'a mut self, obj: &'a ZObj) -> Option<&'a mut V> {
+        let key = obj.as_ptr() as usize;
+        let mut values = self.inner.values.borrow_mut();
+        values
+            .get_mut(&key)
+            .map(|value| unsafe { &mut *(&mut **value as *mut V) })
+    }
+
+    /// Returns true if the map contains the value keyed by the object.
+    pub fn contains_key(&self, obj: &ZObj) -> bool {
+        self.inner
+            .values
+            .borrow()
+            .contains_key(&(obj.as_ptr() as usize))
+    }
+
+    /// Removes the value keyed by the object.
+    pub fn remove(&mut self, obj: &ZObj) -> Option<V> {
+        let key = obj.as_ptr() as usize;
+        let value = self.inner.values.borrow_mut().remove(&key)?;
+        unsafe {
+            phper_zend_weakrefs_hash_del(&mut self.inner.table, obj.as_ptr() as *mut _);
+        }
+        Some(*value)
+    }
+
+    /// Returns the number of the objects in the map.
+    pub fn len(&self) -> usize {
+        self.inner.values.borrow().len()
+    }
+
+    /// Returns true if the map contains no objects.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+impl<V> Default for ZWeakMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+impl<V> Drop for ZWeakMap<V> {
+    fn drop(&mut self) {
+        let keys = self
+            .inner
+            .values
+            .borrow()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        unsafe {
+            for key in keys {
+                phper_zend_weakrefs_hash_del(&mut self.inner.table, key as *mut zend_object);
+            }
+            zend_hash_destroy(&mut self.inner.table);
+        }
+    }
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+unsafe extern "C" fn weak_map_entry_dtor<V>(value: *mut zval) {
+    unsafe {
+        let entry = Box::from_raw(phper_z_ptr_p(value).cast::<WeakMapEntry<V>>());
+        // Release the borrow before dropping the value, which may destroy
+        // other objects in the map.
+        let value = (*entry.values).borrow_mut().remove(&entry.key);
+        drop(value);
+    }
+}
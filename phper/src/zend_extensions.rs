@@ -0,0 +1,403 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+//! Apis relate to [zend_extension].
+//!
+//! The zend extension is loaded by `zend_extension=` in `php.ini`, used by
+//! debuggers and profilers which need the statement and function call
+//! handlers.
+
+use crate::{modules::Module, sys::*, utils::ensure_end_with_zero, values::ExecuteData};
+use std::{
+    ffi::{CStr, CString},
+    mem::{take, zeroed},
+    os::raw::{c_char, c_int},
+    ptr::null_mut,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Global pointer hold the ZendExtension builder.
+static GLOBAL_ZEND_EXTENSION: AtomicPtr<ZendExtension> = AtomicPtr::new(null_mut());
+
+#[inline]
+unsafe fn global_zend_extension<'a>() -> Option<&'a mut ZendExtension> {
+    unsafe { GLOBAL_ZEND_EXTENSION.load(Ordering::Acquire).as_mut() }
+}
+
+#[cfg(phper_zts)]
+type RequestHook = dyn Fn() + Send + Sync;
+
+#[cfg(not(phper_zts))]
+type RequestHook = dyn Fn();
+
+#[cfg(phper_zts)]
+type ExecuteDataHook = dyn Fn(&mut ExecuteData) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+type ExecuteDataHook = dyn Fn(&mut ExecuteData);
+
+#[cfg(phper_zts)]
+type OpArrayHook = dyn Fn(&mut zend_op_array) + Send + Sync;
+
+#[cfg(not(phper_zts))]
+type OpArrayHook = dyn Fn(&mut zend_op_array);
+
+/// Builder for registering zend extension, returned by the function with
+/// attribute [php_zend_extension](crate::php_zend_extension).
+pub struct ZendExtension {
+    name: CString,
+    version: CString,
+    author: CString,
+    url: CString,
+    copyright: CString,
+    startup: Option<Box<dyn FnOnce()>>,
+    shutdown: Option<Box<dyn FnOnce()>>,
+    activate: Option<Box<RequestHook>>,
+    deactivate: Option<Box<RequestHook>>,
+    statement: Option<Box<ExecuteDataHook>>,
+    fcall_begin: Option<Box<ExecuteDataHook>>,
+    fcall_end: Option<Box<ExecuteDataHook>>,
+    op_array: Option<Box<OpArrayHook>>,
+    module: Option<Module>,
+}
+
+impl ZendExtension {
+    /// Construct the `ZendExtension` with base metadata.
+    pub fn new(
+        name: impl Into<String>, version: impl Into<String>, author: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: ensure_end_with_zero(name),
+            version: ensure_end_with_zero(version),
+            author: ensure_end_with_zero(author),
+            url: Default::default(),
+            copyright: Default::default(),
+            startup: None,
+            shutdown: None,
+            activate: None,
+            deactivate: None,
+            statement: None,
+            fcall_begin: None,
+            fcall_end: None,
+            op_array: None,
+            module: None,
+        }
+    }
+
+    /// Set the url shown in `php -v`.
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.url = ensure_end_with_zero(url);
+    }
+
+    /// Set the copyright shown in `php -v`.
+    pub fn set_copyright(&mut self, copyright: impl Into<String>) {
+        self.copyright = ensure_end_with_zero(copyright);
+    }
+
+    /// Register the companion [`Module`], started up when the zend extension
+    /// starts up, so that the functions, classes and module hooks are
+    /// available without loading by `extension=`.
+    pub fn set_module(&mut self, module: Module) {
+        self.module = Some(module);
+    }
+
+    /// Register startup hook, called after the modules started up.
+    pub fn on_startup(&mut self, func: impl FnOnce() + 'static) {
+        self.startup = Some(Box::new(func));
+    }
+
+    /// Register shutdown hook.
+    pub fn on_shutdown(&mut self, func: impl FnOnce() + 'static) {
+        self.shutdown = Some(Box::new(func));
+    }
+
+    /// Register activate hook, called at the beginning of request.
+    #[cfg(phper_zts)]
+    pub fn on_activate(&mut self, func: impl Fn() + Send + Sync + 'static) {
+        self.activate = Some(Box::new(func));
+    }
+
+    /// Register activate hook, called at the beginning of request.
+    #[cfg(not(phper_zts))]
+    pub fn on_activate(&mut self, func: impl Fn() + 'static) {
+        self.activate = Some(Box::new(func));
+    }
+
+    /// Register deactivate hook, called at the end of request.
+    #[cfg(phper_zts)]
+    pub fn on_deactivate(&mut self, func: impl Fn() + Send + Sync + 'static) {
+        self.deactivate = Some(Box::new(func));
+    }
+
+    /// Register deactivate hook, called at the end of request.
+    #[cfg(not(phper_zts))]
+    pub fn on_deactivate(&mut self, func: impl Fn() + 'static) {
+        self.deactivate = Some(Box::new(func));
+    }
+
+    /// Register statement hook, called before every statement executed.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_STMT` when the hook
+    /// is registered.
+    #[cfg(phper_zts)]
+    pub fn on_statement(&mut self, func: impl Fn(&mut ExecuteData) + Send + Sync + 'static) {
+        self.statement = Some(Box::new(func));
+    }
+
+    /// Register statement hook, called before every statement executed.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_STMT` when the hook
+    /// is registered.
+    #[cfg(not(phper_zts))]
+    pub fn on_statement(&mut self, func: impl Fn(&mut ExecuteData) + 'static) {
+        self.statement = Some(Box::new(func));
+    }
+
+    /// Register function call begin hook.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_FCALL` when the
+    /// hook is registered.
+    #[cfg(phper_zts)]
+    pub fn on_fcall_begin(&mut self, func: impl Fn(&mut ExecuteData) + Send + Sync + 'static) {
+        self.fcall_begin = Some(Box::new(func));
+    }
+
+    /// Register function call begin hook.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_FCALL` when the
+    /// hook is registered.
+    #[cfg(not(phper_zts))]
+    pub fn on_fcall_begin(&mut self, func: impl Fn(&mut ExecuteData) + 'static) {
+        self.fcall_begin = Some(Box::new(func));
+    }
+
+    /// Register function call end hook.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_FCALL` when the
+    /// hook is registered.
+    #[cfg(phper_zts)]
+    pub fn on_fcall_end(&mut self, func: impl Fn(&mut ExecuteData) + Send + Sync + 'static) {
+        self.fcall_end = Some(Box::new(func));
+    }
+
+    /// Register function call end hook.
+    ///
+    /// The scripts are compiled with `ZEND_COMPILE_EXTENDED_FCALL` when the
+    /// hook is registered.
+    #[cfg(not(phper_zts))]
+    pub fn on_fcall_end(&mut self, func: impl Fn(&mut ExecuteData) + 'static) {
+        self.fcall_end = Some(Box::new(func));
+    }
+
+    /// Register op array hook, called after every op array compiled.
+    #[cfg(phper_zts)]
+    pub fn on_op_array(&mut self, func: impl Fn(&mut zend_op_array) + Send + Sync + 'static) {
+        self.op_array = Some(Box::new(func));
+    }
+
+    /// Register op array hook, called after every op array compiled.
+    #[cfg(not(phper_zts))]
+    pub fn on_op_array(&mut self, func: impl Fn(&mut zend_op_array) + 'static) {
+        self.op_array = Some(Box::new(func));
+    }
+
+    fn compiler_options(&self) -> u32 {
+        let mut options = 0;
+        #[cfg(phper_major_version = "8")]
+        {
+            if self.statement.is_some() {
+                options |= ZEND_COMPILE_EXTENDED_STMT;
+            }
+            if self.fcall_begin.is_some() || self.fcall_end.is_some() {
+                options |= ZEND_COMPILE_EXTENDED_FCALL;
+            }
+        }
+        #[cfg(phper_major_version = "7")]
+        if self.statement.is_some() || self.fcall_begin.is_some() || self.fcall_end.is_some() {
+            options |= ZEND_COMPILE_EXTENDED_INFO;
+        }
+        options
+    }
+}
+
+/// The `extension_version_info` symbol, emitted by
+/// [php_zend_extension](crate::php_zend_extension).
+#[doc(hidden)]
+#[repr(transparent)]
+pub struct ZendExtensionVersionInfo(zend_extension_version_info);
+
+unsafe impl Sync for ZendExtensionVersionInfo {}
+
+impl ZendExtensionVersionInfo {
+    /// The build id can't be evaluated in const context, so it's left empty
+    /// and checked by `build_id_check` of the entry.
+    pub const fn new() -> Self {
+        Self(zend_extension_version_info {
+            zend_extension_api_no: ZEND_EXTENSION_API_NO as c_int,
+            build_id: c"".as_ptr().cast_mut(),
+        })
+    }
+}
+
+impl Default for ZendExtensionVersionInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `zend_extension_entry` symbol, emitted by
+/// [php_zend_extension](crate::php_zend_extension).
+#[doc(hidden)]
+#[repr(transparent)]
+pub struct ZendExtensionEntry(zend_extension);
+
+unsafe impl Sync for ZendExtensionEntry {}
+
+impl ZendExtensionEntry {
+    /// The name and version are shown when the loading failed, and replaced
+    /// by the ones of [`ZendExtension`] in startup.
+    pub const fn new(
+        name: &'static CStr, version: &'static CStr,
+        startup: unsafe extern "C" fn(*mut zend_extension) -> c_int,
+    ) -> Self {
+        let mut entry: zend_extension = unsafe { zeroed() };
+        entry.name = name.as_ptr().cast_mut();
+        entry.version = version.as_ptr().cast_mut();
+        entry.startup = Some(startup);
+        entry.shutdown = Some(shutdown);
+        entry.activate = Some(activate);
+        entry.deactivate = Some(deactivate);
+        entry.op_array_handler = Some(op_array_handler);
+        entry.statement_handler = Some(statement_handler);
+        entry.fcall_begin_handler = Some(fcall_begin_handler);
+        entry.fcall_end_handler = Some(fcall_end_handler);
+        entry.build_id_check = Some(build_id_check);
+        Self(entry)
+    }
+
+    /// Called by the startup function emitted by
+    /// [php_zend_extension](crate::php_zend_extension).
+    ///
+    /// # Safety
+    ///
+    /// Should only be called by the zend extension startup.
+    pub unsafe fn startup(
+        extension: *mut zend_extension, get_zend_extension: fn() -> ZendExtension,
+    ) -> c_int {
+        unsafe {
+            let zend_extension = Box::into_raw(Box::new(get_zend_extension()));
+            if GLOBAL_ZEND_EXTENSION
+                .compare_exchange(
+                    null_mut(),
+                    zend_extension,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_err()
+            {
+                drop(Box::from_raw(zend_extension));
+                return ZEND_RESULT_CODE_FAILURE;
+            }
+            let zend_extension = &mut *zend_extension;
+
+            let extension = &mut *extension;
+            extension.name = zend_extension.name.as_ptr().cast_mut();
+            extension.version = zend_extension.version.as_ptr().cast_mut();
+            extension.author = zend_extension.author.as_ptr().cast_mut();
+            extension.URL = zend_extension.url.as_ptr().cast_mut();
+            extension.copyright = zend_extension.copyright.as_ptr().cast_mut();
+
+            if let Some(module) = take(&mut zend_extension.module) {
+                let entry = module.module_entry();
+                if zend_startup_module(entry.cast_mut()) != ZEND_RESULT_CODE_SUCCESS {
+                    return ZEND_RESULT_CODE_FAILURE;
+                }
+            }
+
+            if let Some(f) = take(&mut zend_extension.startup) {
+                f();
+            }
+
+            ZEND_RESULT_CODE_SUCCESS
+        }
+    }
+}
+
+unsafe extern "C" fn shutdown(_extension: *mut zend_extension) {
+    unsafe {
+        if let Some(zend_extension) = global_zend_extension() {
+            if let Some(f) = take(&mut zend_extension.shutdown) {
+                f();
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn activate() {
+    unsafe {
+        if let Some(zend_extension) = global_zend_extension() {
+            crate::cg!(compiler_options) |= zend_extension.compiler_options();
+
+            if let Some(f) = &zend_extension.activate {
+                f();
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn deactivate() {
+    unsafe {
+        if let Some(f) = global_zend_extension().and_then(|ext| ext.deactivate.as_ref()) {
+            f();
+        }
+    }
+}
+
+unsafe extern "C" fn op_array_handler(op_array: *mut zend_op_array) {
+    unsafe {
+        if let Some(f) = global_zend_extension().and_then(|ext| ext.op_array.as_ref()) {
+            f(&mut *op_array);
+        }
+    }
+}
+
+unsafe extern "C" fn statement_handler(execute_data: *mut zend_execute_data) {
+    unsafe {
+        if let Some(f) = global_zend_extension().and_then(|ext| ext.statement.as_ref()) {
+            f(ExecuteData::from_mut_ptr(execute_data));
+        }
+    }
+}
+
+unsafe extern "C" fn fcall_begin_handler(execute_data: *mut zend_execute_data) {
+    unsafe {
+        if let Some(f) = global_zend_extension().and_then(|ext| ext.fcall_begin.as_ref()) {
+            f(ExecuteData::from_mut_ptr(execute_data));
+        }
+    }
+}
+
+unsafe extern "C" fn fcall_end_handler(execute_data: *mut zend_execute_data) {
+    unsafe {
+        if let Some(f) = global_zend_extension().and_then(|ext| ext.fcall_end.as_ref()) {
+            f(ExecuteData::from_mut_ptr(execute_data));
+        }
+    }
+}
+
+unsafe extern "C" fn build_id_check(build_id: *const c_char) -> c_int {
+    unsafe {
+        if CStr::from_ptr(build_id) == CStr::from_ptr(phper_get_zend_extension_build_id()) {
+            ZEND_RESULT_CODE_SUCCESS
+        } else {
+            ZEND_RESULT_CODE_FAILURE
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+#![cfg(phper_major_version = "8")]
+
+use phper::{
+    attributes::{Attribute, AttributeTarget},
+    classes::{ClassEntity, Visibility},
+    functions::Argument,
+    modules::Module,
+    types::{ArgumentTypeHint, Scalar},
+};
+
+pub fn integrate(module: &mut Module) {
+    let mut route = ClassEntity::new(r"IntegrationTest\Attributes\Route");
+    route.set_attribute_class([AttributeTarget::Function, AttributeTarget::Method], true);
+    route.add_property("path", Visibility::Public, "");
+    route
+        .add_method("__construct", Visibility::Public, |this, arguments| {
+            this.set_property("path", arguments[0].clone());
+            phper::ok(())
+        })
+        .argument(Argument::new("path"));
+    module.add_class(route);
+
+    module
+        .add_function("integrate_attributes_login", |_| phper::ok(()))
+        .argument(Argument::new("user"))
+        .argument(Argument::new("password").with_attribute(r"\SensitiveParameter"))
+        .add_attribute(Attribute::new(r"IntegrationTest\Attributes\Route").argument("/login"))
+        .add_attribute(
+            Attribute::new(r"IntegrationTest\Attributes\Route").named_argument("path", "/signin"),
+        );
+
+    let mut class = ClassEntity::new(r"IntegrationTest\Attributes\Foo");
+    class.add_attribute("AllowDynamicProperties");
+    class
+        .add_method("bar", Visibility::Public, |_, _| phper::ok(()))
+        .add_attribute(Attribute::new(r"IntegrationTest\Attributes\Route").argument("/bar"));
+    class
+        .add_typed_property("baz", Visibility::Public, ArgumentTypeHint::Int)
+        .add_attribute(
+            Attribute::new("Meta")
+                .argument(1)
+                .named_argument("tags", Scalar::list(["a", "b"])),
+        );
+    module.add_class(class);
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{
+    arrays::ZArray,
+    backtrace::{CallType, backtrace},
+    modules::Module,
+    values::ZVal,
+};
+
+pub fn integrate(module: &mut Module) {
+    module.add_function("integrate_backtrace", |arguments| {
+        let with_args = arguments[0].expect_bool()?;
+
+        let mut frames = ZArray::new();
+        for frame in backtrace(with_args) {
+            let mut arr = ZArray::new();
+            arr.insert("function", ZVal::from(frame.function()));
+            if let Some(class) = frame.class() {
+                arr.insert("class", ZVal::from(class));
+            }
+            match frame.call_type() {
+                Some(CallType::Object) => arr.insert("type", ZVal::from("->")),
+                Some(CallType::Static) => arr.insert("type", ZVal::from("::")),
+                None => {}
+            }
+            if let Some(file) = frame.file() {
+                arr.insert("file", ZVal::from(file));
+            }
+            if let Some(line) = frame.line() {
+                arr.insert("line", ZVal::from(line as i64));
+            }
+            if let Some(args) = frame.args() {
+                let mut args_arr = ZArray::new();
+                for arg in args {
+                    args_arr.insert((), arg.clone());
+                }
+                arr.insert("args", ZVal::from(args_arr));
+            }
+            frames.insert((), ZVal::from(arr));
+        }
+
+        Ok::<_, phper::Error>(frames)
+    });
+}
@@ -10,10 +10,12 @@
 
 use phper::{
     alloc::RefClone,
-    classes::{ClassEntity, ClassEntry, Interface, InterfaceEntity, StateClass, Visibility},
+    classes::{
+        ClassEntity, ClassEntry, Interface, InterfaceEntity, StateClass, TraitEntity, Visibility,
+    },
     functions::{Argument, ReturnType},
     modules::Module,
-    types::{ArgumentTypeHint, ReturnTypeHint},
+    types::{ArgumentTypeHint, ReturnTypeHint, Scalar},
     values::ZVal,
 };
 use std::{collections::HashMap, convert::Infallible};
@@ -26,6 +28,10 @@ pub fn integrate(module: &mut Module) {
     integrate_i_constants(module);
     integrate_bar_extends_foo(module, foo_class);
     integrate_dependent_classes(module);
+    integrate_modifiers(module);
+    integrate_typed_properties(module);
+    integrate_traits(module);
+    integrate_magic_methods(module);
     #[cfg(phper_major_version = "8")]
     integrate_stringable(module);
 }
@@ -42,6 +48,14 @@ fn integrate_a(module: &mut Module) {
     class.add_constant("CST_FALSE", false);
     class.add_constant("CST_INT", 100);
     class.add_constant("CST_FLOAT", 10.0);
+    class.add_constant(
+        "CST_ARRAY",
+        Scalar::from_iter([
+            ("foo", Scalar::from("bar")),
+            ("list", Scalar::list([1i64, 2, 3])),
+        ]),
+    );
+    class.add_property("tags", Visibility::Public, Scalar::list(["a", "b"]));
 
     class
         .add_method("__construct", Visibility::Public, |this, arguments| {
@@ -261,6 +275,127 @@ fn integrate_dependent_classes(module: &mut Module) {
     module.add_class(b_cls);
 }
 
+fn integrate_modifiers(module: &mut Module) {
+    let mut final_class = ClassEntity::new(r"IntegrationTest\Modifiers\FinalClass");
+    final_class.set_final();
+    module.add_class(final_class);
+
+    let mut abstract_class = ClassEntity::new(r"IntegrationTest\Modifiers\AbstractClass");
+    abstract_class.set_abstract();
+    abstract_class
+        .add_method("finalMethod", Visibility::Public, |_, _| phper::ok("final"))
+        .set_final();
+    abstract_class
+        .add_method("deprecatedMethod", Visibility::Public, |_, _| {
+            phper::ok("deprecated")
+        })
+        .set_deprecated();
+    abstract_class.add_abstract_static_method("abstractStaticMethod", Visibility::Public);
+    module.add_class(abstract_class);
+
+    #[cfg(all(
+        phper_major_version = "8",
+        not(any(phper_minor_version = "0", phper_minor_version = "1"))
+    ))]
+    {
+        let mut readonly_class = ClassEntity::new(r"IntegrationTest\Modifiers\ReadonlyClass");
+        readonly_class.set_readonly();
+        module.add_class(readonly_class);
+    }
+}
+
+fn integrate_traits(module: &mut Module) {
+    let mut counter = TraitEntity::new(r"IntegrationTest\Traits\Counter");
+    counter.add_property("count", Visibility::Private, 0);
+    counter.add_method("increase", Visibility::Public, |this, _| {
+        let count = this.get_property("count").expect_long()? + 1;
+        this.set_property("count", count);
+        Ok::<_, phper::Error>(count)
+    });
+    counter.add_method("describe", Visibility::Public, |this, _| {
+        let label = this.call("label", &mut [])?;
+        Ok::<_, phper::Error>(format!("counter of {}", label.expect_z_str()?.to_str()?))
+    });
+    counter.add_static_method("kind", Visibility::Public, |_| phper::ok("counter"));
+    counter.add_abstract_method("label", Visibility::Public);
+    let counter = module.add_trait(counter);
+
+    let mut class = ClassEntity::new(r"IntegrationTest\Traits\RustCounter");
+    class.uses(counter);
+    class.add_method("label", Visibility::Public, |_, _| phper::ok("rust"));
+    module.add_class(class);
+}
+
+fn integrate_magic_methods(module: &mut Module) {
+    let mut class = ClassEntity::<HashMap<String, ZVal>>::new_with_default_state_constructor(
+        r"IntegrationTest\MagicMethods",
+    );
+
+    class.on_get(|this, name| {
+        let value = this.as_state().get(name.to_str()?).cloned();
+        Ok::<_, phper::Error>(value.unwrap_or_default())
+    });
+    class.on_set(|this, name, value| {
+        this.as_mut_state()
+            .insert(name.to_str()?.to_owned(), value.clone());
+        Ok::<_, phper::Error>(())
+    });
+    class
+        .on_isset(|this, name| Ok::<_, phper::Error>(this.as_state().contains_key(name.to_str()?)));
+    class.on_unset(|this, name| {
+        this.as_mut_state().remove(name.to_str()?);
+        Ok::<_, phper::Error>(())
+    });
+    class.on_call(|this, name, arguments| {
+        Ok::<_, phper::Error>(format!(
+            "{}({}) with {} properties",
+            name.to_str()?,
+            arguments.len(),
+            this.as_state().len()
+        ))
+    });
+    class.on_call_static(|name, arguments| {
+        Ok::<_, phper::Error>(format!("static {}({})", name.to_str()?, arguments.len()))
+    });
+    class.on_invoke(|this, arguments| {
+        Ok::<_, phper::Error>(arguments.len() as i64 + this.as_state().len() as i64)
+    });
+
+    module.add_class(class);
+}
+
+fn integrate_typed_properties(module: &mut Module) {
+    let mut class = ClassEntity::new(r"IntegrationTest\TypedProperties");
+
+    class
+        .add_typed_property("count", Visibility::Public, ArgumentTypeHint::Int)
+        .default_value(1);
+    class
+        .add_typed_property("name", Visibility::Public, ArgumentTypeHint::String)
+        .allow_null();
+    class.add_typed_property(
+        "foo",
+        Visibility::Public,
+        ArgumentTypeHint::ClassEntry(r"IntegrationTest\Foo".to_owned()),
+    );
+    class
+        .add_static_typed_property("instances", Visibility::Public, ArgumentTypeHint::Int)
+        .default_value(0);
+
+    #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+    {
+        class
+            .add_typed_property("id", Visibility::Public, ArgumentTypeHint::Int)
+            .set_readonly();
+        class.add_method("__construct", Visibility::Public, |this, _| {
+            this.set_property("id", 10);
+            phper::ok(())
+        });
+    }
+
+    module.add_class(class);
+}
+
 #[cfg(phper_major_version = "8")]
 fn integrate_stringable(module: &mut Module) {
     use phper::{functions::ReturnType, types::ReturnTypeHint};
@@ -8,7 +8,7 @@
 // NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
 // See the Mulan PSL v2 for more details.
 
-use phper::modules::Module;
+use phper::{classes::ClassEntry, constants, modules::Module, types::Scalar};
 
 pub fn integrate(module: &mut Module) {
     module.add_constant("INTEGRATE_CONST_NULL", ());
@@ -18,4 +18,33 @@ pub fn integrate(module: &mut Module) {
     module.add_constant("INTEGRATE_CONST_DOUBLE", 200.);
     module.add_constant("INTEGRATE_CONST_STRING", "something");
     module.add_constant("INTEGRATE_CONST_BYTES", "something".as_bytes().to_owned());
+    module.add_constant(
+        "INTEGRATE_CONST_ARRAY",
+        Scalar::from_iter([
+            ("name", Scalar::from("phper")),
+            ("10", Scalar::from(true)),
+            ("nested", Scalar::from_iter([("a", Scalar::list([1.5f64]))])),
+        ]),
+    );
+
+    #[cfg(phper_major_version = "8")]
+    module
+        .add_constant("INTEGRATE_CONST_DEPRECATED", 1i64)
+        .deprecated();
+
+    #[cfg(phper_php_8_5_or_later)]
+    module
+        .add_constant("INTEGRATE_CONST_ATTRIBUTE", 1i64)
+        .add_attribute("IntegrateConstAttribute");
+
+    module.add_function("integrate_constants_get", |arguments| {
+        let name = arguments[0].expect_str()?;
+        Ok::<_, phper::Error>(constants::get(name))
+    });
+
+    module.add_function("integrate_constants_get_class_constant", |arguments| {
+        let class_name = arguments[0].expect_str()?;
+        let name = arguments[1].expect_str()?;
+        Ok::<_, phper::Error>(ClassEntry::from_globals(class_name)?.get_constant(name))
+    });
 }
@@ -11,9 +11,10 @@
 #![cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
 
 use phper::{
+    PhpEnum,
     alloc::ToRefOwned,
     classes::Visibility,
-    enums::{Enum, EnumEntity},
+    enums::{Enum, EnumEntity, PhpEnum as _},
     modules::Module,
     objects::ZObject,
 };
@@ -34,6 +35,11 @@ pub fn integrate(module: &mut Module) {
 
     // Add test for EnumCase
     test_enum_case(module);
+
+    // Add test for enum methods, case lookup and derive
+    test_enum_methods(module);
+    test_enum_lookup(module);
+    test_derive_php_enum(module);
 }
 
 fn create_pure_enum(module: &mut Module) {
@@ -86,7 +92,7 @@ fn test_enum_from_name(module: &mut Module) {
         let mut pure_enum = Enum::from_name("IntegrationTest\\PureEnum");
 
         // Test the get_case method and convert the result to ZObject
-        let one_case = unsafe { pure_enum.get_mut_case("ONE")? };
+        let one_case = pure_enum.get_mut_case("ONE")?;
         let zobj: ZObject = one_case.to_ref_owned();
 
         Ok::<_, phper::Error>(zobj)
@@ -98,7 +104,7 @@ fn test_enum_from_name(module: &mut Module) {
         let mut int_enum = Enum::from_name("IntegrationTest\\IntEnum");
 
         // Test the get_case method and convert the result to ZObject
-        let low_case = unsafe { int_enum.get_mut_case("LOW")? };
+        let low_case = int_enum.get_mut_case("LOW")?;
         let zobj: ZObject = low_case.to_ref_owned();
 
         Ok::<_, phper::Error>(zobj)
@@ -110,7 +116,7 @@ fn test_enum_from_name(module: &mut Module) {
         let mut string_enum = Enum::from_name("IntegrationTest\\StringEnum");
 
         // Test the get_case method and convert the result to ZObject
-        let red_case = unsafe { string_enum.get_mut_case("RED")? };
+        let red_case = string_enum.get_mut_case("RED")?;
         let zobj: ZObject = red_case.to_ref_owned();
 
         Ok::<_, phper::Error>(zobj)
@@ -179,3 +185,70 @@ fn test_enum_case(module: &mut Module) {
         }
     });
 }
+
+fn test_enum_methods(module: &mut Module) {
+    let mut enum_entity = EnumEntity::<String>::new("IntegrationTest\\Suit");
+    enum_entity.add_case("Hearts", "H".to_string());
+    enum_entity.add_case("Spades", "S".to_string());
+
+    enum_entity.add_method("label", Visibility::Public, |this, arguments| {
+        let value = this.value().unwrap().expect_z_str()?.to_str()?;
+        let prefix = match arguments.first() {
+            Some(prefix) => prefix.expect_z_str()?.to_str()?.to_owned(),
+            None => String::new(),
+        };
+        Ok::<_, phper::Error>(format!("{}{} ({})", prefix, this.name().to_str()?, value))
+    });
+
+    module.add_enum(enum_entity);
+}
+
+fn test_enum_lookup(module: &mut Module) {
+    module.add_function("test_enum_try_from", |args| {
+        let int_enum = Enum::from_name("IntegrationTest\\IntEnum");
+        let case_obj = int_enum.try_from(args[0].expect_long()?)?;
+        Ok::<_, phper::Error>(case_obj.to_ref_owned())
+    });
+
+    module.add_function("test_enum_cases", |_args| {
+        let string_enum = Enum::from_name("IntegrationTest\\StringEnum");
+        let names = string_enum
+            .cases()
+            .into_iter()
+            .map(|case_obj| {
+                Ok(case_obj
+                    .get_property("name")
+                    .expect_z_str()?
+                    .to_str()?
+                    .to_owned())
+            })
+            .collect::<phper::Result<Vec<_>>>()?;
+        Ok::<_, phper::Error>(names.join(","))
+    });
+
+    module.add_function("test_enum_get_missing_case", |_args| {
+        let pure_enum = Enum::from_name("IntegrationTest\\PureEnum");
+        pure_enum.get_case("FOUR")?;
+        Ok::<_, phper::Error>(())
+    });
+}
+
+#[derive(PhpEnum, PartialEq)]
+#[php_enum(name = "IntegrationTest\\Level")]
+enum Level {
+    #[php_enum(name = "LOW")]
+    Low = 1,
+    #[php_enum(name = "HIGH")]
+    High = 10,
+}
+
+fn test_derive_php_enum(module: &mut Module) {
+    module.add_enum(Level::enum_entity());
+
+    module.add_function("test_derive_enum_high", |_args| Level::High.to_zval());
+
+    module.add_function("test_derive_enum_is_low", |args| {
+        let level = Level::try_from(&args[0])?;
+        Ok::<_, phper::Error>(level == Level::Low)
+    });
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{
+    arrays::ZArray,
+    functions::call,
+    modules::Module,
+    values::{ExecuteData, ZVal},
+};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+static TRACED_COUNT: AtomicI64 = AtomicI64::new(0);
+static TRACED_LAST_ARG: AtomicI64 = AtomicI64::new(0);
+static HELPER_COUNT: AtomicI64 = AtomicI64::new(0);
+static INTERNAL_COUNT: AtomicI64 = AtomicI64::new(0);
+
+fn function_name(execute_data: &ExecuteData) -> String {
+    execute_data
+        .func()
+        .get_function_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+pub fn integrate(module: &mut Module) {
+    module.on_execute_ex(|mut execute| {
+        let execute_data = execute.execute_data_mut();
+        match &*function_name(execute_data) {
+            "integrate_execute_traced" => {
+                TRACED_COUNT.fetch_add(1, Ordering::SeqCst);
+                if let Some(arg) = execute_data.get_parameter(0).as_long() {
+                    TRACED_LAST_ARG.store(arg, Ordering::SeqCst);
+                }
+                // Calling user function inside the hook mustn't trigger the
+                // hook again.
+                call("integrate_execute_helper", []).unwrap();
+            }
+            "integrate_execute_helper" => {
+                HELPER_COUNT.fetch_add(1, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+        execute.call();
+    });
+
+    module.on_execute_internal(|execute| {
+        let is_target =
+            function_name(execute.execute_data()) == "integrate_execute_internal_target";
+        let return_value = execute.call();
+        if is_target {
+            INTERNAL_COUNT.fetch_add(1, Ordering::SeqCst);
+            if let Some(n) = return_value.as_mut_long() {
+                *n += 1;
+            }
+        }
+    });
+
+    module.add_function("integrate_execute_internal_target", |arguments| {
+        Ok::<_, phper::Error>(arguments[0].expect_long()? * 2)
+    });
+
+    module.add_function("integrate_execute_counts", |_| {
+        let mut counts = ZArray::new();
+        counts.insert("traced", ZVal::from(TRACED_COUNT.load(Ordering::SeqCst)));
+        counts.insert(
+            "traced_last_arg",
+            ZVal::from(TRACED_LAST_ARG.load(Ordering::SeqCst)),
+        );
+        counts.insert("helper", ZVal::from(HELPER_COUNT.load(Ordering::SeqCst)));
+        counts.insert(
+            "internal",
+            ZVal::from(INTERNAL_COUNT.load(Ordering::SeqCst)),
+        );
+        Ok::<_, phper::Error>(counts)
+    });
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+#![cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+
+use phper::{arrays::ZArray, fibers::ZFiber, functions::Argument, modules::Module};
+
+pub fn integrate(module: &mut Module) {
+    module
+        .add_function("integrate_fibers_drive", |arguments| {
+            let mut fiber = ZFiber::new(arguments[0].clone())?;
+            let mut values = ZArray::new();
+
+            let mut value = fiber.start([arguments[1].clone()])?;
+            while !fiber.is_terminated() {
+                assert!(fiber.is_suspended());
+                let resumed = value.expect_long()? * 10;
+                values.insert((), value);
+                value = fiber.resume(resumed)?;
+            }
+            values.insert((), fiber.get_return()?);
+
+            Ok::<_, phper::Error>(values)
+        })
+        .arguments([Argument::new("callable"), Argument::new("argument")]);
+
+    module
+        .add_function("integrate_fibers_start", |arguments| {
+            ZFiber::new(arguments[0].clone())?.start([])
+        })
+        .argument(Argument::new("callable"));
+
+    module
+        .add_function("integrate_fibers_suspend", |arguments| {
+            ZFiber::suspend(arguments[0].clone())
+        })
+        .argument(Argument::new("value"));
+
+    module.add_function("integrate_fibers_current", |_| {
+        let fiber = ZFiber::current();
+        if let Some(fiber) = &fiber {
+            assert!(fiber.is_started());
+            assert!(fiber.is_running());
+        }
+        Ok::<_, phper::Error>(fiber)
+    });
+
+    module
+        .add_function("integrate_fibers_is_fiber", |arguments| {
+            let obj = arguments[0].expect_mut_z_obj()?;
+            Ok::<_, phper::Error>(ZFiber::from_z_obj(obj).is_some())
+        })
+        .argument(Argument::new("obj"));
+}
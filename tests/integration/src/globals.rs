@@ -0,0 +1,34 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{arrays::ZArray, globals, modules::Module, values::ZVal};
+
+pub fn integrate(module: &mut Module) {
+    module.add_function("integrate_globals_get", |arguments| {
+        let name = arguments[0].expect_str()?;
+        Ok::<_, phper::Error>(globals::get(name))
+    });
+
+    module.add_function("integrate_globals_set", |arguments| {
+        let name = arguments[0].expect_str()?.to_owned();
+        globals::set_global(&name, arguments[1].clone());
+        phper::ok(())
+    });
+
+    module.add_function("integrate_globals_superglobals", |_| {
+        let mut arr = ZArray::new();
+        arr.insert("_GET", ZVal::from(globals::get_vars()));
+        arr.insert("_POST", ZVal::from(globals::post_vars()));
+        arr.insert("_COOKIE", ZVal::from(globals::cookie_vars()));
+        arr.insert("_SERVER", ZVal::from(globals::server_vars()));
+        arr.insert("_ENV", ZVal::from(globals::env_vars()));
+        phper::ok(arr)
+    });
+}
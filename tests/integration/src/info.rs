@@ -0,0 +1,29 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::modules::Module;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+static INFO_CALLED: AtomicI64 = AtomicI64::new(0);
+
+pub fn integrate(module: &mut Module) {
+    module.on_info(|info| {
+        let called = INFO_CALLED.fetch_add(1, Ordering::SeqCst) + 1;
+
+        info.table()
+            .colspan_header(3, "Integration Pools")
+            .header(["Pool", "Size", "Idle"])
+            .row(["default", "10", "8"])
+            .row(["backup", "5", ""]);
+
+        info.table()
+            .row(["info called".to_owned(), called.to_string()]);
+    });
+}
@@ -9,10 +9,10 @@
 // See the Mulan PSL v2 for more details.
 
 use phper::{
-    ini::{Policy, ini_get},
+    ini::{Policy, Quantity, ini_get},
     modules::Module,
 };
-use std::{convert::Infallible, ffi::CStr};
+use std::{convert::Infallible, ffi::CStr, time::Duration};
 
 pub fn integrate(module: &mut Module) {
     module.add_ini("INTEGRATE_INI_TRUE", true, Policy::System);
@@ -25,6 +25,16 @@ pub fn integrate(module: &mut Module) {
         Policy::System,
     );
 
+    module
+        .add_ini("INTEGRATE_INI_QUANTITY", "128M".to_owned(), Policy::System)
+        .displayer(|value| format!("{} bytes", value.parse::<Quantity>().unwrap().bytes()));
+    module.add_ini("INTEGRATE_INI_DURATION", "1.5s".to_owned(), Policy::System);
+    module.add_ini(
+        "INTEGRATE_INI_LIST",
+        "foo, bar,,baz".to_owned(),
+        Policy::System,
+    );
+
     module.add_function("integrate_ini_assert", |_| {
         assert!(ini_get::<bool>("INTEGRATE_INI_TRUE"));
         assert!(!ini_get::<bool>("INTEGRATE_INI_FALSE"));
@@ -34,6 +44,28 @@ pub fn integrate(module: &mut Module) {
             ini_get::<Option<&CStr>>("INTEGRATE_INI_STRING"),
             Some(c"something")
         );
+        assert_eq!(ini_get::<String>("INTEGRATE_INI_STRING"), "something");
+        assert_eq!(ini_get::<Option<String>>("INTEGRATE_INI_NOT_EXISTS"), None);
+        assert_eq!(
+            ini_get::<Quantity>("INTEGRATE_INI_QUANTITY").bytes(),
+            128 * 1024 * 1024
+        );
+        assert_eq!(
+            ini_get::<Duration>("INTEGRATE_INI_DURATION"),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            ini_get::<Vec<String>>("INTEGRATE_INI_LIST"),
+            vec!["foo".to_owned(), "bar".to_owned(), "baz".to_owned()]
+        );
         Ok::<_, Infallible>(())
     });
+
+    let pool_size = module.add_ini_with("INTEGRATE_INI_POOL_SIZE", 10i64, Policy::All, |value| {
+        value.parse::<u64>()
+    });
+
+    module.add_function("integrate_ini_pool_size", move |_| {
+        Ok::<_, Infallible>(pool_size.get() as i64)
+    });
 }
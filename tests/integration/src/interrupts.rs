@@ -0,0 +1,55 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{functions::Argument, interrupts, modules::Module};
+use std::{
+    hint::black_box,
+    sync::mpsc::{RecvTimeoutError, channel},
+    thread,
+    time::Duration,
+};
+
+pub fn integrate(module: &mut Module) {
+    module.add_function("integrate_interrupts_check", |_| interrupts::check());
+
+    module
+        .add_function("integrate_interrupts_sum", |arguments| {
+            let n = arguments[0].expect_long()?;
+            let mut sum = 0;
+            for i in interrupts::interruptible(0..n) {
+                sum += i?;
+            }
+            Ok::<_, phper::Error>(sum)
+        })
+        .argument(Argument::new("n"));
+
+    module.add_function("integrate_interrupts_busy_loop", |_| {
+        for i in interrupts::interruptible(0i64..) {
+            black_box(i?);
+        }
+        Ok::<_, phper::Error>(())
+    });
+
+    module
+        .add_function("integrate_interrupts_wait", |arguments| {
+            let millis = arguments[0].expect_long()? as u64;
+            let (tx, rx) = channel();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(millis));
+                let _ = tx.send("done");
+            });
+            interrupts::wait(|timeout| match rx.recv_timeout(timeout) {
+                Ok(message) => Some(Some(message)),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => Some(None),
+            })
+        })
+        .argument(Argument::new("millis"));
+}
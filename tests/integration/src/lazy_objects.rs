@@ -0,0 +1,57 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+#![cfg(phper_php_8_4_or_later)]
+
+use phper::{classes::ClassEntry, functions::Argument, modules::Module};
+
+pub fn integrate(module: &mut Module) {
+    module
+        .add_function("integrate_lazy_objects_ghost", |arguments| {
+            let class_name = arguments[0].expect_z_str()?.to_str()?.to_owned();
+            let value = arguments[1].clone();
+            ClassEntry::from_globals(class_name)?.new_lazy_ghost(move |obj| {
+                obj.set_property("value", value);
+                Ok::<_, phper::Error>(())
+            })
+        })
+        .arguments([Argument::new("class_name"), Argument::new("value")]);
+
+    module
+        .add_function("integrate_lazy_objects_proxy", |arguments| {
+            let class_name = arguments[0].expect_z_str()?.to_str()?.to_owned();
+            let mut value = [arguments[1].clone()];
+            ClassEntry::from_globals(&class_name)?.new_lazy_proxy(move |_| {
+                ClassEntry::from_globals(&class_name)?.new_object(&mut value)
+            })
+        })
+        .arguments([Argument::new("class_name"), Argument::new("value")]);
+
+    module
+        .add_function("integrate_lazy_objects_failed", |arguments| {
+            let class_name = arguments[0].expect_z_str()?.to_str()?;
+            ClassEntry::from_globals(class_name)?
+                .new_lazy_ghost(|_| Err(phper::Error::boxed("failed to initialize")))
+        })
+        .argument(Argument::new("class_name"));
+
+    module
+        .add_function("integrate_lazy_objects_is_uninitialized", |arguments| {
+            let obj = arguments[0].expect_z_obj()?;
+            Ok::<_, phper::Error>(obj.is_uninitialized_lazy_object())
+        })
+        .argument(Argument::new("obj"));
+
+    module
+        .add_function("integrate_lazy_objects_initialize", |arguments| {
+            arguments[0].expect_mut_z_obj()?.initialize_lazy_object()
+        })
+        .argument(Argument::new("obj"));
+}
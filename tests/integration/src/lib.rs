@@ -12,18 +12,31 @@
 
 mod arguments;
 mod arrays;
+mod attributes;
+mod backtrace;
 mod classes;
 mod constants;
+mod dependencies;
 mod enums;
 mod errors;
+mod execute;
+mod fibers;
 mod functions;
+mod globals;
+mod info;
 mod ini;
+mod interrupts;
+mod lazy_objects;
 mod macros;
 mod objects;
 mod references;
+mod replace;
+mod runtime;
+mod sapi;
 mod strings;
 mod typehints;
 mod values;
+mod weakrefs;
 
 use phper::{modules::Module, php_get_module};
 
@@ -48,8 +61,33 @@ pub fn get_module() -> Module {
     errors::integrate(&mut module);
     references::integrate(&mut module);
     typehints::integrate(&mut module);
+    execute::integrate(&mut module);
+    replace::integrate(&mut module);
+    backtrace::integrate(&mut module);
+    dependencies::integrate(&mut module);
+    info::integrate(&mut module);
+    sapi::integrate(&mut module);
+    globals::integrate(&mut module);
+    runtime::integrate(&mut module);
+    interrupts::integrate(&mut module);
+    #[cfg(not(all(
+        phper_major_version = "7",
+        any(
+            phper_minor_version = "0",
+            phper_minor_version = "1",
+            phper_minor_version = "2",
+            phper_minor_version = "3",
+        )
+    )))]
+    weakrefs::integrate(&mut module);
+    #[cfg(phper_major_version = "8")]
+    attributes::integrate(&mut module);
     #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
     enums::integrate(&mut module);
+    #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+    fibers::integrate(&mut module);
+    #[cfg(phper_php_8_4_or_later)]
+    lazy_objects::integrate(&mut module);
 
     module
 }
@@ -10,6 +10,7 @@
 
 use phper::{
     alloc::{RefClone, ToRefOwned},
+    arrays::ZArray,
     classes::{ClassEntity, ClassEntry, Visibility},
     functions::Argument,
     modules::Module,
@@ -145,6 +146,87 @@ pub fn integrate(module: &mut Module) {
         phper::ok(())
     });
 
+    module.add_function("integrate_objects_has_unset_props", |_| {
+        let mut o = ZObject::new_by_std_class();
+
+        o.set_property("foo", "bar");
+        o.set_property("empty", "");
+        o.set_property("null", ());
+        assert!(o.has_property("foo", false));
+        assert!(o.has_property("foo", true));
+        assert!(o.has_property("empty", false));
+        assert!(!o.has_property("empty", true));
+        assert!(!o.has_property("null", false));
+        assert!(!o.has_property("no_exists", false));
+
+        o.unset_property("foo");
+        assert!(!o.has_property("foo", false));
+
+        phper::ok(())
+    });
+
+    module
+        .add_function("integrate_objects_properties", |arguments| {
+            let o = arguments[0].expect_z_obj()?;
+            let properties = o
+                .properties()
+                .iter()
+                .map(|(name, value, vis)| {
+                    let vis = match vis {
+                        Visibility::Public => "public",
+                        Visibility::Protected => "protected",
+                        Visibility::Private => "private",
+                    };
+                    Ok(format!("{} {}={}", vis, name, value.expect_long()?))
+                })
+                .collect::<phper::Result<Vec<_>>>()?;
+            phper::ok(properties.join(","))
+        })
+        .argument(Argument::new("obj"));
+
+    module
+        .add_function("integrate_objects_scoped_property", |arguments| {
+            let scope = ClassEntry::from_globals(arguments[1].expect_z_str()?.to_str()?)?;
+            let name = arguments[2].expect_z_str()?.to_str()?.to_owned();
+            let o = arguments[0].expect_z_obj()?;
+            phper::ok(o.get_scoped_property(scope, name))
+        })
+        .arguments([
+            Argument::new("obj"),
+            Argument::new("scope"),
+            Argument::new("name"),
+        ]);
+
+    module
+        .add_function("integrate_objects_scoped_has_unset", |arguments| {
+            let scope = ClassEntry::from_globals(arguments[1].expect_z_str()?.to_str()?)?;
+            let name = arguments[2].expect_z_str()?.to_str()?.to_owned();
+            let o = arguments[0].expect_mut_z_obj()?;
+            let mut result = ZArray::new();
+            result.insert((), o.has_scoped_property(scope, &name, false));
+            o.unset_scoped_property(scope, &name);
+            result.insert((), o.has_scoped_property(scope, &name, false));
+            phper::ok(result)
+        })
+        .arguments([
+            Argument::new("obj"),
+            Argument::new("scope"),
+            Argument::new("name"),
+        ]);
+
+    module
+        .add_function(
+            "integrate_objects_instantiate_with_properties",
+            |arguments| {
+                let class_name = arguments[0].expect_z_str()?.to_str()?.to_owned();
+                let properties = arguments[1].expect_z_arr()?;
+                let object = ClassEntry::from_globals(class_name)?
+                    .instantiate_with_properties(properties)?;
+                phper::ok(object)
+            },
+        )
+        .arguments([Argument::new("class_name"), Argument::new("properties")]);
+
     let class_a =
         ClassEntity::new_with_state_constructor("IntegrationTest\\Objects\\A", || 123456i64);
     module.add_class(class_a);
@@ -0,0 +1,60 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{
+    classes::{ClassEntity, Visibility},
+    functions::Argument,
+    modules::Module,
+    values::ZVal,
+};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+static ARRAY_OBJECT_COUNT_CALLS: AtomicI64 = AtomicI64::new(0);
+
+pub fn integrate(module: &mut Module) {
+    // Replace the functions and methods owned by the test, so that the other
+    // tests are not affected.
+    module
+        .add_function("integrate_replace_greet", |arguments: &mut [ZVal]| {
+            let name = arguments[0].expect_z_str()?.to_str()?.to_owned();
+            phper::ok(format!("hello {}", name))
+        })
+        .argument(Argument::new("name"));
+
+    let mut counter = ClassEntity::new(r"IntegrationTest\Replace\Counter");
+    counter.add_method("count", Visibility::Public, |_, _| phper::ok(3));
+    module.add_class(counter);
+
+    module.replace_function("integrate_replace_greet", |original| {
+        let return_value = original.call();
+        let replaced = format!(
+            "{}!",
+            return_value.expect_z_str().unwrap().to_str().unwrap()
+        );
+        *return_value = ZVal::from(replaced);
+    });
+
+    module.replace_method(r"IntegrationTest\Replace\Counter", "count", |original| {
+        if let Some(n) = original.call().as_mut_long() {
+            *n += 100;
+        }
+    });
+
+    // Replace the method of the built-in class, only count the calls without
+    // changing the result, so that the other tests are not affected.
+    module.replace_method("ArrayObject", "count", |original| {
+        ARRAY_OBJECT_COUNT_CALLS.fetch_add(1, Ordering::SeqCst);
+        original.call();
+    });
+
+    module.add_function("integrate_replace_array_object_count_calls", |_| {
+        phper::ok(ARRAY_OBJECT_COUNT_CALLS.load(Ordering::SeqCst))
+    });
+}
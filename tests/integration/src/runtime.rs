@@ -0,0 +1,49 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{functions::Argument, modules::Module, runtime};
+use std::time::Duration;
+use tokio::{runtime::Builder, time::sleep};
+
+pub fn integrate(module: &mut Module) {
+    module.enable_runtime(|| {
+        let mut builder = Builder::new_multi_thread();
+        builder.worker_threads(2);
+        builder
+    });
+
+    module
+        .add_function("integrate_runtime_block_on", |arguments| {
+            let millis = arguments[0].expect_long()?;
+            runtime::block_on(async move {
+                sleep(Duration::from_millis(millis as u64)).await;
+                millis * 2
+            })
+        })
+        .argument(Argument::new("millis"));
+
+    module.add_function("integrate_runtime_spawn", |_| {
+        let handle = runtime::handle()?;
+        let tasks = (1..=3i64)
+            .map(|i| handle.spawn(async move { i * 10 }))
+            .collect::<Vec<_>>();
+        runtime::block_on(async move {
+            let mut sum = 0;
+            for task in tasks {
+                sum += task.await.map_err(phper::Error::boxed)?;
+            }
+            Ok::<_, phper::Error>(sum)
+        })?
+    });
+
+    module.add_function("integrate_runtime_nested_block_on", |_| {
+        runtime::block_on(async { runtime::block_on(async {}) })?
+    });
+}
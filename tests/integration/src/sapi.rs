@@ -0,0 +1,57 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+use phper::{arrays::ZArray, modules::Module, sapi, values::ZVal};
+
+pub fn integrate(module: &mut Module) {
+    module.add_function("integrate_sapi_name", |_| phper::ok(sapi::name()));
+
+    module.add_function("integrate_sapi_request", |_| {
+        let mut arr = ZArray::new();
+        arr.insert("method", ZVal::from(sapi::request_method()));
+        arr.insert("uri", ZVal::from(sapi::request_uri()));
+        arr.insert("query_string", ZVal::from(sapi::query_string()));
+        arr.insert("content_type", ZVal::from(sapi::content_type()));
+        arr.insert("content_length", ZVal::from(sapi::content_length()));
+        phper::ok(arr)
+    });
+
+    module.add_function("integrate_sapi_request_body", |_| {
+        phper::ok(sapi::read_request_body())
+    });
+
+    module.add_function("integrate_sapi_headers_sent", |_| {
+        phper::ok(sapi::headers_sent())
+    });
+
+    module.add_function("integrate_sapi_no_headers", |_| {
+        phper::ok(sapi::no_headers())
+    });
+
+    module.add_function("integrate_sapi_add_header", |arguments| {
+        let header = arguments[0].expect_z_str()?.to_str()?.to_owned();
+        let replace = arguments[1].expect_bool()?;
+        Ok::<_, phper::Error>(sapi::add_header(&header, replace)?)
+    });
+
+    module.add_function("integrate_sapi_remove_header", |arguments| {
+        let name = arguments[0].expect_z_str()?.to_str()?.to_owned();
+        Ok::<_, phper::Error>(sapi::remove_header(&name)?)
+    });
+
+    module.add_function("integrate_sapi_response_code", |_| {
+        phper::ok(sapi::response_code() as i64)
+    });
+
+    module.add_function("integrate_sapi_set_response_code", |arguments| {
+        let code = arguments[0].expect_long()?;
+        Ok::<_, phper::Error>(sapi::set_response_code(code as i32)?)
+    });
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+#![cfg(not(all(
+    phper_major_version = "7",
+    any(
+        phper_minor_version = "0",
+        phper_minor_version = "1",
+        phper_minor_version = "2",
+        phper_minor_version = "3",
+    )
+)))]
+
+use phper::{
+    classes::{ClassEntity, Visibility},
+    functions::Argument,
+    modules::Module,
+    weakrefs::ZWeakRef,
+};
+use std::convert::Infallible;
+
+pub fn integrate(module: &mut Module) {
+    integrate_weak_ref(module);
+    #[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+    integrate_weak_map(module);
+}
+
+fn integrate_weak_ref(module: &mut Module) {
+    module
+        .add_function("integrate_weakrefs_upgrade", |arguments| {
+            let obj = arguments[0].expect_mut_z_obj()?;
+            let weak = ZWeakRef::new(obj);
+            let upgraded = weak.upgrade().map(|upgraded| upgraded.handle());
+            Ok::<_, phper::Error>(upgraded == Some(obj.handle()))
+        })
+        .argument(Argument::new("obj"));
+
+    let mut class = ClassEntity::<Vec<ZWeakRef>>::new_with_default_state_constructor(
+        r"IntegrationTest\WeakRefs\Dispatcher",
+    );
+
+    class
+        .add_method("addListener", Visibility::Public, |this, arguments| {
+            let weak = ZWeakRef::new(arguments[0].expect_mut_z_obj()?);
+            this.as_mut_state().push(weak);
+            Ok::<_, phper::Error>(())
+        })
+        .argument(Argument::new("listener"));
+
+    class.add_method("count", Visibility::Public, |this, _| {
+        let count = this
+            .as_state()
+            .iter()
+            .filter(|weak| weak.upgrade().is_some())
+            .count();
+        Ok::<_, Infallible>(count as i64)
+    });
+
+    module.add_class(class);
+}
+
+#[cfg(all(phper_major_version = "8", not(phper_minor_version = "0")))]
+fn integrate_weak_map(module: &mut Module) {
+    use phper::{values::ZVal, weakrefs::ZWeakMap};
+
+    let mut class = ClassEntity::<ZWeakMap<ZVal>>::new_with_default_state_constructor(
+        r"IntegrationTest\WeakRefs\Metadata",
+    );
+
+    class
+        .add_method("set", Visibility::Public, |this, arguments| {
+            let value = arguments[1].clone();
+            let obj = arguments[0].expect_mut_z_obj()?;
+            Ok::<_, phper::Error>(this.as_mut_state().insert(obj, value).unwrap_or_default())
+        })
+        .arguments([Argument::new("obj"), Argument::new("value")]);
+
+    class
+        .add_method("get", Visibility::Public, |this, arguments| {
+            let obj = arguments[0].expect_z_obj()?;
+            Ok::<_, phper::Error>(this.as_state().get(obj).cloned().unwrap_or_default())
+        })
+        .argument(Argument::new("obj"));
+
+    class
+        .add_method("remove", Visibility::Public, |this, arguments| {
+            let obj = arguments[0].expect_z_obj()?;
+            Ok::<_, phper::Error>(this.as_mut_state().remove(obj).unwrap_or_default())
+        })
+        .argument(Argument::new("obj"));
+
+    class
+        .add_method("has", Visibility::Public, |this, arguments| {
+            let obj = arguments[0].expect_z_obj()?;
+            Ok::<_, phper::Error>(this.as_state().contains_key(obj))
+        })
+        .argument(Argument::new("obj"));
+
+    class.add_method("count", Visibility::Public, |this, _| {
+        Ok::<_, Infallible>(this.as_state().len() as i64)
+    });
+
+    module.add_class(class);
+}
@@ -10,8 +10,8 @@
 
 mod common;
 
-use crate::common::{DYLIB_PATH, TESTS_PHP_DIR};
-use phper_test::cli::test_php_script;
+use crate::common::{CONFLICTS_DYLIB_PATH, DYLIB_PATH, TESTS_PHP_DIR};
+use phper_test::cli::{test_php_script, test_php_script_with_condition, test_php_script_with_ini};
 
 #[test]
 fn test_phpinfo() {
@@ -28,6 +28,11 @@ fn test_arrays() {
     test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("arrays.php"));
 }
 
+#[test]
+fn test_attributes() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("attributes.php"));
+}
+
 #[test]
 fn test_classes() {
     test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("classes.php"));
@@ -83,6 +88,28 @@ fn test_reflection() {
     test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("reflection.php"));
 }
 
+#[test]
+fn test_dependencies_conflicts() {
+    // The conflicting extension is loaded before, so this extension can't be
+    // loaded.
+    test_php_script_with_ini(
+        &[
+            "display_startup_errors=On",
+            &format!("extension={}", CONFLICTS_DYLIB_PATH.display()),
+            &format!("extension={}", DYLIB_PATH.display()),
+        ],
+        TESTS_PHP_DIR.join("dependencies_conflicts.php"),
+        |output| {
+            let message = r#"Cannot load module "integration" because conflicting module "integration_conflicts" is already loaded"#;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            (stdout.contains(message) || stderr.contains(message))
+                && stdout.contains("bool(false)\nbool(true)")
+                && output.status.success()
+        },
+    );
+}
+
 #[test]
 fn test_typehints() {
     test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("typehints.php"));
@@ -92,3 +119,69 @@ fn test_typehints() {
 fn test_enums() {
     test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("enums.php"));
 }
+
+#[test]
+fn test_execute() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("execute.php"));
+}
+
+#[test]
+fn test_replace() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("replace.php"));
+}
+
+#[test]
+fn test_backtrace() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("backtrace.php"));
+}
+
+#[test]
+fn test_sapi() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("sapi.php"));
+}
+
+#[test]
+fn test_globals() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("globals.php"));
+}
+
+#[test]
+fn test_weakrefs() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("weakrefs.php"));
+}
+
+#[test]
+fn test_lazy_objects() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("lazy_objects.php"));
+}
+
+#[test]
+fn test_fibers() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("fibers.php"));
+}
+
+#[test]
+fn test_runtime() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("runtime.php"));
+}
+
+#[test]
+fn test_interrupts() {
+    test_php_script(&*DYLIB_PATH, TESTS_PHP_DIR.join("interrupts.php"));
+}
+
+#[test]
+fn test_interrupts_timeout() {
+    test_php_script_with_condition(
+        &*DYLIB_PATH,
+        TESTS_PHP_DIR.join("interrupts_timeout.php"),
+        |output| {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = "Maximum execution time of 1 second exceeded";
+            (stdout.contains(message) || stderr.contains(message))
+                && !stdout.contains("unreachable")
+                && !output.status.success()
+        },
+    );
+}
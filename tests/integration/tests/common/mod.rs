@@ -23,6 +23,17 @@ pub static DYLIB_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     result.get_cdylib().unwrap()
 });
 
+/// The extension conflicted by the `integration` extension.
+#[allow(dead_code)]
+pub static CONFLICTS_DYLIB_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    log::setup();
+    let result = CargoBuilder::new()
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../integration-conflicts"))
+        .build()
+        .unwrap();
+    result.get_cdylib().unwrap()
+});
+
 pub static TESTS_PHP_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
     Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("tests")
@@ -33,6 +33,13 @@ async fn test_arrays() {
         .await;
 }
 
+#[tokio::test]
+async fn test_attributes() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/attributes.php", None, None)
+        .await;
+}
+
 #[tokio::test]
 async fn test_classes() {
     FPM_HANDLE
@@ -116,3 +123,79 @@ async fn test_enums() {
         .test_fpm_request("GET", &*TESTS_PHP_DIR, "/enums.php", None, None)
         .await;
 }
+
+#[tokio::test]
+async fn test_execute() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/execute.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_replace() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/replace.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_backtrace() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/backtrace.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_sapi() {
+    FPM_HANDLE
+        .test_fpm_request(
+            "POST",
+            &*TESTS_PHP_DIR,
+            "/sapi.php?foo=bar",
+            Some("application/json".to_owned()),
+            Some(br#"{"hello":"world"}"#.to_vec()),
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn test_globals() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/globals.php?foo=bar", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_weakrefs() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/weakrefs.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_lazy_objects() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/lazy_objects.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_fibers() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/fibers.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_runtime() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/runtime.php", None, None)
+        .await;
+}
+
+#[tokio::test]
+async fn test_interrupts() {
+    FPM_HANDLE
+        .test_fpm_request("GET", &*TESTS_PHP_DIR, "/interrupts.php", None, None)
+        .await;
+}
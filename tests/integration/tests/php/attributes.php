@@ -0,0 +1,56 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+// PHP 8.0+ is required to use attributes feature
+if (PHP_VERSION_ID < 80000) {
+    echo "PHP 8.0+ required for attribute tests\n";
+    exit(0);
+}
+
+// Function attributes.
+$login = new ReflectionFunction("integrate_attributes_login");
+$routes = $login->getAttributes();
+assert_eq(count($routes), 2);
+assert_eq($routes[0]->getName(), "IntegrationTest\\Attributes\\Route");
+assert_eq($routes[0]->getArguments(), ["/login"]);
+assert_eq($routes[1]->getArguments(), ["path" => "/signin"]);
+assert_eq($routes[0]->newInstance()->path, "/login");
+assert_eq($routes[1]->newInstance()->path, "/signin");
+
+// Parameter attributes.
+$parameters = $login->getParameters();
+assert_eq(count($parameters[0]->getAttributes()), 0);
+$sensitive = $parameters[1]->getAttributes();
+assert_eq(count($sensitive), 1);
+assert_eq($sensitive[0]->getName(), "SensitiveParameter");
+
+// Attribute class.
+$route_class = new ReflectionClass(IntegrationTest\Attributes\Route::class);
+$attribute = $route_class->getAttributes()[0];
+assert_eq($attribute->getName(), "Attribute");
+assert_eq($attribute->getArguments(), [Attribute::TARGET_FUNCTION | Attribute::TARGET_METHOD | Attribute::IS_REPEATABLE]);
+
+// Class, method and property attributes.
+$foo_class = new ReflectionClass(IntegrationTest\Attributes\Foo::class);
+assert_eq($foo_class->getAttributes()[0]->getName(), "AllowDynamicProperties");
+
+$bar = $foo_class->getMethod("bar")->getAttributes();
+assert_eq(count($bar), 1);
+assert_eq($bar[0]->newInstance()->path, "/bar");
+
+$baz = $foo_class->getProperty("baz")->getAttributes();
+assert_eq(count($baz), 1);
+assert_eq($baz[0]->getName(), "Meta");
+assert_eq($baz[0]->getArguments(), [1, "tags" => ["a", "b"]]);
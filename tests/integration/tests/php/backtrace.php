@@ -0,0 +1,60 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+function integrate_backtrace_a($x) {
+    return integrate_backtrace_b($x, 2, 3);
+}
+
+function integrate_backtrace_b($x, $y) {
+    return integrate_backtrace(true);
+}
+
+class IntegrateBacktrace {
+    public function foo() {
+        return integrate_backtrace_a(1);
+    }
+
+    public static function bar() {
+        return (new IntegrateBacktrace())->foo();
+    }
+}
+
+$frames = IntegrateBacktrace::bar();
+assert_eq(count($frames), 5);
+
+assert_eq($frames[0]['function'], 'integrate_backtrace');
+assert_eq($frames[0]['file'], __FILE__);
+assert_eq($frames[0]['line'], 21);
+assert_eq($frames[0]['args'], [true]);
+
+assert_eq($frames[1]['function'], 'integrate_backtrace_b');
+assert_eq($frames[1]['line'], 17);
+assert_eq($frames[1]['args'], [1, 2, 3]);
+
+assert_eq($frames[2]['function'], 'integrate_backtrace_a');
+assert_eq($frames[2]['args'], [1]);
+
+assert_eq($frames[3]['function'], 'foo');
+assert_eq($frames[3]['class'], 'IntegrateBacktrace');
+assert_eq($frames[3]['type'], '->');
+
+assert_eq($frames[4]['function'], 'bar');
+assert_eq($frames[4]['class'], 'IntegrateBacktrace');
+assert_eq($frames[4]['type'], '::');
+assert_eq($frames[4]['line'], 34);
+
+$frames = integrate_backtrace(false);
+assert_eq(count($frames), 1);
+assert_false(isset($frames[0]['args']));
@@ -97,6 +97,16 @@ assert_true(true, IntegrationTest\A::CST_TRUE);
 assert_false(false, IntegrationTest\A::CST_FALSE);
 assert_eq(100, IntegrationTest\A::CST_INT);
 assert_eq(10.0, IntegrationTest\A::CST_FLOAT);
+assert_eq(['foo' => 'bar', 'list' => [1, 2, 3]], IntegrationTest\A::CST_ARRAY);
+$cst_array = IntegrationTest\A::CST_ARRAY;
+$cst_array['list'][] = 4;
+assert_eq([1, 2, 3], IntegrationTest\A::CST_ARRAY['list']);
+
+$tags_a = new \IntegrationTest\A("foo", 1);
+assert_eq(['a', 'b'], $tags_a->tags);
+$tags_a->tags[] = 'c';
+assert_eq(['a', 'b', 'c'], $tags_a->tags);
+assert_eq(['a', 'b'], (new \IntegrationTest\A("bar", 2))->tags);
 
 // Test interface constants
 assert_true(interface_exists(IntegrationTest\IConstants::class));
@@ -117,3 +127,118 @@ $b = IntegrationTest\Dependency\A::createB();
 assert_true($b instanceof IntegrationTest\Dependency\B);
 $a = IntegrationTest\Dependency\B::createA();
 assert_true($a instanceof IntegrationTest\Dependency\A);
+
+// Test class and method modifiers
+$final_class = new ReflectionClass(IntegrationTest\Modifiers\FinalClass::class);
+assert_true($final_class->isFinal());
+assert_false($final_class->isAbstract());
+
+$abstract_class = new ReflectionClass(IntegrationTest\Modifiers\AbstractClass::class);
+assert_true($abstract_class->isAbstract());
+assert_false($abstract_class->isFinal());
+assert_false($abstract_class->isInstantiable());
+assert_throw(function () { new IntegrationTest\Modifiers\AbstractClass(); }, "Error", 0, "Cannot instantiate abstract class IntegrationTest\\Modifiers\\AbstractClass");
+
+$final_method = $abstract_class->getMethod("finalMethod");
+assert_true($final_method->isFinal());
+assert_false($final_method->isStatic());
+assert_true($abstract_class->getMethod("deprecatedMethod")->isDeprecated());
+$abstract_static_method = $abstract_class->getMethod("abstractStaticMethod");
+assert_true($abstract_static_method->isAbstract());
+assert_true($abstract_static_method->isStatic());
+
+class IntegrationTestModifiersConcrete extends IntegrationTest\Modifiers\AbstractClass {
+    public static function abstractStaticMethod() {}
+}
+
+$concrete = new IntegrationTestModifiersConcrete();
+assert_eq($concrete->finalMethod(), "final");
+assert_eq(@$concrete->deprecatedMethod(), "deprecated");
+
+if (PHP_VERSION_ID >= 80200) {
+    $readonly_class = new ReflectionClass(IntegrationTest\Modifiers\ReadonlyClass::class);
+    assert_true($readonly_class->isReadOnly());
+    assert_throw(function () {
+        $object = new IntegrationTest\Modifiers\ReadonlyClass();
+        $object->foo = 1;
+    }, "Error", 0, "Cannot create dynamic property IntegrationTest\\Modifiers\\ReadonlyClass::\$foo");
+}
+
+// Test typed properties
+if (PHP_VERSION_ID >= 70400) {
+    $typed = new IntegrationTest\TypedProperties();
+    $typed_class = new ReflectionClass(IntegrationTest\TypedProperties::class);
+
+    assert_eq($typed->count, 1);
+    assert_eq($typed_class->getProperty("count")->getType()->getName(), "int");
+    assert_false($typed_class->getProperty("count")->getType()->allowsNull());
+    assert_throw(function () use ($typed) { $typed->count = []; }, "TypeError", 0, PHP_VERSION_ID >= 80000
+        ? "Cannot assign array to property IntegrationTest\\TypedProperties::\$count of type int"
+        : "Typed property IntegrationTest\\TypedProperties::\$count must be int, array used");
+
+    assert_eq($typed_class->getProperty("name")->getType()->getName(), "string");
+    assert_true($typed_class->getProperty("name")->getType()->allowsNull());
+    assert_false($typed_class->getProperty("name")->isInitialized($typed));
+    assert_throw(function () use ($typed) { return $typed->name; }, "Error", 0, "Typed property IntegrationTest\\TypedProperties::\$name must not be accessed before initialization");
+    $typed->name = null;
+    assert_eq($typed->name, null);
+
+    assert_eq($typed_class->getProperty("foo")->getType()->getName(), "IntegrationTest\\Foo");
+    $typed->foo = new IntegrationTest\Foo();
+    assert_true($typed->foo instanceof IntegrationTest\Foo);
+
+    assert_eq(IntegrationTest\TypedProperties::$instances, 0);
+    assert_true($typed_class->getProperty("instances")->isStatic());
+}
+
+if (PHP_VERSION_ID >= 80100) {
+    $id = $typed_class->getProperty("id");
+    assert_true($id->isReadOnly());
+    assert_eq($typed->id, 10);
+    assert_throw(function () use ($typed) { $typed->id = 11; }, "Error", 0, "Cannot modify readonly property IntegrationTest\\TypedProperties::\$id");
+}
+
+// Test traits
+class IntegrationTestTraitsUserCounter {
+    use IntegrationTest\Traits\Counter {
+        increase as protected;
+        increase as public next;
+    }
+
+    public function label() {
+        return "user";
+    }
+}
+
+$trait_class = new ReflectionClass(IntegrationTest\Traits\Counter::class);
+assert_true($trait_class->isTrait());
+
+$user_counter = new IntegrationTestTraitsUserCounter();
+assert_eq($user_counter->next(), 1);
+assert_eq($user_counter->next(), 2);
+assert_eq($user_counter->describe(), "counter of user");
+assert_eq(IntegrationTestTraitsUserCounter::kind(), "counter");
+assert_true((new ReflectionMethod(IntegrationTestTraitsUserCounter::class, "increase"))->isProtected());
+
+$rust_counter = new IntegrationTest\Traits\RustCounter();
+assert_eq($rust_counter->increase(), 1);
+assert_eq($rust_counter->increase(), 2);
+assert_eq($rust_counter->describe(), "counter of rust");
+assert_eq(IntegrationTest\Traits\RustCounter::kind(), "counter");
+assert_true((new ReflectionClass(IntegrationTest\Traits\RustCounter::class))->hasProperty("count"));
+
+// Test magic methods
+$magic = new IntegrationTest\MagicMethods();
+assert_false(isset($magic->foo));
+assert_eq($magic->foo, null);
+$magic->foo = "bar";
+assert_true(isset($magic->foo));
+assert_eq($magic->foo, "bar");
+assert_eq($magic->hello(1, 2), "hello(2) with 1 properties");
+assert_eq(IntegrationTest\MagicMethods::world(1), "static world(1)");
+assert_eq($magic(1, 2, 3), 4);
+unset($magic->foo);
+assert_false(isset($magic->foo));
+assert_false(method_exists($magic, "__get"));
+assert_false(method_exists($magic, "__call"));
+assert_true(method_exists($magic, "__invoke"));
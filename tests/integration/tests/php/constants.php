@@ -20,3 +20,46 @@ assert_eq(INTEGRATE_CONST_LONG, 100);
 assert_eq(INTEGRATE_CONST_DOUBLE, 200.0);
 assert_eq(INTEGRATE_CONST_STRING, "something");
 assert_eq(INTEGRATE_CONST_BYTES, "something");
+assert_eq(INTEGRATE_CONST_ARRAY, ["name" => "phper", 10 => true, "nested" => ["a" => [1.5]]]);
+assert_true(array_key_exists(10, INTEGRATE_CONST_ARRAY));
+$const_array = INTEGRATE_CONST_ARRAY;
+$const_array["nested"]["a"][] = 2.5;
+assert_eq(INTEGRATE_CONST_ARRAY["nested"]["a"], [1.5]);
+
+define('INTEGRATE_CONST_DEFINED', [1, 2]);
+
+class IntegrateConstants {
+    const FOO = 'foo';
+    const BAR = self::FOO . 'bar';
+    private const BAZ = 'baz';
+}
+
+assert_eq(integrate_constants_get('PHP_VERSION'), PHP_VERSION);
+assert_eq(integrate_constants_get('E_ALL'), E_ALL);
+assert_eq(integrate_constants_get('INTEGRATE_CONST_LONG'), 100);
+assert_eq(integrate_constants_get('INTEGRATE_CONST_DEFINED'), [1, 2]);
+assert_eq(integrate_constants_get('IntegrateConstants::BAR'), 'foobar');
+assert_eq(integrate_constants_get('INTEGRATE_CONST_NOT_EXISTS'), null);
+assert_eq(integrate_constants_get('IntegrateConstantsNotExists::FOO'), null);
+
+assert_eq(integrate_constants_get_class_constant('IntegrateConstants', 'FOO'), 'foo');
+assert_eq(integrate_constants_get_class_constant('IntegrateConstants', 'BAR'), 'foobar');
+assert_eq(integrate_constants_get_class_constant('IntegrateConstants', 'BAZ'), 'baz');
+assert_eq(integrate_constants_get_class_constant('IntegrateConstants', 'NOT_EXISTS'), null);
+
+if (PHP_VERSION_ID >= 80000) {
+    $deprecated = null;
+    set_error_handler(function ($errno, $errstr) use (&$deprecated) {
+        $deprecated = $errstr;
+        return true;
+    }, E_DEPRECATED);
+    assert_eq(INTEGRATE_CONST_DEPRECATED, 1);
+    restore_error_handler();
+    assert_eq($deprecated, 'Constant INTEGRATE_CONST_DEPRECATED is deprecated');
+}
+
+if (PHP_VERSION_ID >= 80500) {
+    $attributes = (new ReflectionConstant('INTEGRATE_CONST_ATTRIBUTE'))->getAttributes();
+    assert_eq(count($attributes), 1);
+    assert_eq($attributes[0]->getName(), 'IntegrateConstAttribute');
+}
@@ -94,3 +94,21 @@ assert_true($string_case instanceof IntegrationTest\TestStringEnum, 'Should retu
 assert_eq($string_case->name, 'RED', 'Should be the RED case');
 assert_eq($string_case->value, 'red', 'RED value should be "red"');
 assert_eq($string_case, IntegrationTest\TestStringEnum::RED, 'Should be equal to the enum case');
+
+// Test enum methods
+assert_eq(IntegrationTest\Suit::Hearts->label(), 'Hearts (H)');
+assert_eq(IntegrationTest\Suit::Spades->label('Suit: '), 'Suit: Spades (S)');
+
+// Test case lookup by value and cases
+assert_eq(test_enum_try_from(5), IntegrationTest\IntEnum::MEDIUM);
+assert_throw(function () { test_enum_try_from(2); }, 'ValueError', 0, '2 is not a valid case of enum IntegrationTest\IntEnum');
+assert_eq(test_enum_cases(), 'RED,GREEN,BLUE');
+assert_throw(function () { test_enum_get_missing_case(); }, 'ValueError', 0, 'FOUR is not a valid case of enum IntegrationTest\PureEnum');
+
+// Test derived enum
+assert_true(enum_exists('IntegrationTest\Level'), 'Level should exist');
+assert_eq(IntegrationTest\Level::LOW->value, 1);
+assert_eq(test_derive_enum_high(), IntegrationTest\Level::HIGH);
+assert_true(test_derive_enum_is_low(IntegrationTest\Level::LOW));
+assert_false(test_derive_enum_is_low(IntegrationTest\Level::HIGH));
+assert_throw(function () { test_derive_enum_is_low(IntegrationTest\IntEnum::LOW); }, 'TypeError', 0, 'type error: must be of type IntegrationTest\Level, IntegrationTest\IntEnum given');
@@ -0,0 +1,36 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+function integrate_execute_traced(int $n): int {
+    return $n + 1;
+}
+
+function integrate_execute_helper(): void {
+}
+
+$before = integrate_execute_counts();
+
+assert_eq(integrate_execute_traced(10), 11);
+assert_eq(integrate_execute_traced(20), 21);
+integrate_execute_helper();
+
+$after = integrate_execute_counts();
+assert_eq($after['traced'] - $before['traced'], 2);
+assert_eq($after['traced_last_arg'], 20);
+// Only the direct call is hooked, the calls from inside the hook are not.
+assert_eq($after['helper'] - $before['helper'], 1);
+
+assert_eq(integrate_execute_internal_target(10), 21);
+assert_eq(integrate_execute_counts()['internal'] - $after['internal'], 1);
@@ -0,0 +1,59 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+// PHP 8.1+ is required to use fibers
+if (PHP_VERSION_ID < 80100) {
+    echo "PHP 8.1+ required for fibers tests\n";
+    exit(0);
+}
+
+// Test driving the fiber from Rust.
+$values = integrate_fibers_drive(function ($a) {
+    $b = Fiber::suspend($a + 1);
+    $c = Fiber::suspend($b + 1);
+    return $c + 1;
+}, 1);
+assert_eq($values, [2, 21, 211]);
+
+// Test suspending the fiber from Rust handler.
+$fiber = new Fiber(function () {
+    return integrate_fibers_suspend("from rust");
+});
+assert_eq($fiber->start(), "from rust");
+assert_true($fiber->isSuspended());
+$fiber->resume("back");
+assert_true($fiber->isTerminated());
+assert_eq($fiber->getReturn(), "back");
+
+// Test suspending outside of fibers.
+assert_throw(function () { integrate_fibers_suspend(1); }, "FiberError", 0, "Cannot suspend outside of fiber");
+
+// Test the current fiber.
+assert_eq(integrate_fibers_current(), null);
+$fiber = new Fiber(function () {
+    return integrate_fibers_current();
+});
+$fiber->start();
+assert_true($fiber->getReturn() === $fiber);
+
+assert_true(integrate_fibers_is_fiber($fiber));
+assert_false(integrate_fibers_is_fiber(new stdClass()));
+
+// Test the exception thrown in the fiber.
+assert_throw(function () {
+    integrate_fibers_start(function () {
+        throw new Exception("boom", 1);
+    });
+}, "Exception", 1, "boom");
@@ -0,0 +1,48 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+$integrate_globals_foo = 1;
+assert_eq(integrate_globals_get('integrate_globals_foo'), 1);
+assert_eq(integrate_globals_get('integrate_globals_not_exists'), null);
+
+integrate_globals_set('integrate_globals_foo', 2);
+assert_eq($integrate_globals_foo, 2);
+
+integrate_globals_set('integrate_globals_bar', 'bar');
+assert_eq($GLOBALS['integrate_globals_bar'], 'bar');
+
+$integrate_globals_baz = [1];
+$integrate_globals_baz_ref = &$integrate_globals_baz;
+integrate_globals_set('integrate_globals_baz_ref', [1, 2]);
+assert_eq($integrate_globals_baz, [1, 2]);
+
+function integrate_globals_in_function() {
+    assert_eq(integrate_globals_get('integrate_globals_foo'), 2);
+    integrate_globals_set('integrate_globals_foo', 3);
+}
+integrate_globals_in_function();
+assert_eq($integrate_globals_foo, 3);
+
+assert_eq(integrate_globals_get('_SERVER')['PHP_SELF'], $_SERVER['PHP_SELF']);
+
+$superglobals = integrate_globals_superglobals();
+assert_eq($superglobals['_GET'], $_GET);
+assert_eq($superglobals['_POST'], $_POST);
+assert_eq($superglobals['_COOKIE'], $_COOKIE);
+assert_eq($superglobals['_SERVER']['PHP_SELF'], $_SERVER['PHP_SELF']);
+assert_eq($superglobals['_ENV'], $_ENV);
+
+if (PHP_SAPI != 'cli') {
+    assert_eq($superglobals['_GET']['foo'], 'bar');
+}
@@ -20,3 +20,15 @@ assert_eq(ini_get("INTEGRATE_INI_DOUBLE"), '200');
 assert_eq(ini_get("INTEGRATE_INI_STRING"), "something");
 
 integrate_ini_assert();
+
+assert_eq(integrate_ini_pool_size(), 10);
+assert_eq(@ini_set("INTEGRATE_INI_POOL_SIZE", "abc"), false);
+assert_eq(ini_get("INTEGRATE_INI_POOL_SIZE"), "10");
+assert_eq(integrate_ini_pool_size(), 10);
+assert_eq(ini_set("INTEGRATE_INI_POOL_SIZE", "20"), "10");
+assert_eq(integrate_ini_pool_size(), 20);
+
+ob_start();
+(new ReflectionExtension("integration"))->info();
+$info = ob_get_clean();
+assert_true(strpos($info, (string) (128 * 1024 * 1024) . " bytes") !== false);
@@ -0,0 +1,70 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+// PHP 8.4+ is required to use lazy objects
+if (PHP_VERSION_ID < 80400) {
+    echo "PHP 8.4+ required for lazy objects tests\n";
+    exit(0);
+}
+
+class IntegrationTestLazyService {
+    public $value;
+
+    public function __construct($value) {
+        $this->value = $value;
+    }
+}
+
+// Test lazy ghost, initialized when the property is accessed.
+$ghost = integrate_lazy_objects_ghost(IntegrationTestLazyService::class, "ghost");
+assert_true($ghost instanceof IntegrationTestLazyService);
+assert_true(integrate_lazy_objects_is_uninitialized($ghost));
+assert_true((new ReflectionClass($ghost))->isUninitializedLazyObject($ghost));
+assert_eq($ghost->value, "ghost");
+assert_false(integrate_lazy_objects_is_uninitialized($ghost));
+
+// Test lazy proxy, forwarding to the real instance.
+$proxy = integrate_lazy_objects_proxy(IntegrationTestLazyService::class, "proxy");
+assert_true(integrate_lazy_objects_is_uninitialized($proxy));
+integrate_lazy_objects_initialize($proxy);
+assert_false(integrate_lazy_objects_is_uninitialized($proxy));
+assert_eq($proxy->value, "proxy");
+$proxy->value = "changed";
+assert_eq($proxy->value, "changed");
+
+// Test the non lazy object.
+$obj = new IntegrationTestLazyService("eager");
+assert_false(integrate_lazy_objects_is_uninitialized($obj));
+integrate_lazy_objects_initialize($obj);
+
+// Test the uninitialized lazy object is destroyed.
+$ghost = integrate_lazy_objects_ghost(IntegrationTestLazyService::class, "dropped");
+unset($ghost);
+
+// Test the exception thrown by the initializer, and the object is still lazy.
+$failed = integrate_lazy_objects_failed(IntegrationTestLazyService::class);
+assert_throw(function () use ($failed) { $failed->value; }, "ErrorException", 0, "failed to initialize");
+assert_true(integrate_lazy_objects_is_uninitialized($failed));
+
+// The initializer is consumed even if failed.
+assert_throw(function () use ($failed) { $failed->value; }, "ErrorException", 0, "the initializer of the lazy object has been dropped");
+
+// Test the internal class can't be lazy.
+try {
+    integrate_lazy_objects_ghost(ArrayObject::class, null);
+    throw new AssertionError("internal class can't be lazy");
+} catch (Error $e) {
+    assert_eq(get_class($e), "Error");
+}
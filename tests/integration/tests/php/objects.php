@@ -20,6 +20,101 @@ integrate_objects_call();
 integrate_objects_to_ref_owned(new stdClass());
 integrate_objects_to_ref_clone(new stdClass());
 integrate_objects_set_props();
+integrate_objects_has_unset_props();
+
+class IntegrationTestObjectsParent {
+    private $secret = 1;
+}
+
+#[AllowDynamicProperties]
+class IntegrationTestObjectsChild extends IntegrationTestObjectsParent {
+    public $a = 2;
+    protected $b = 3;
+    private $c = 4;
+    public $unset = 5;
+}
+
+$child = new IntegrationTestObjectsChild();
+$child->dynamic = 6;
+unset($child->unset);
+$properties = explode(",", integrate_objects_properties($child));
+sort($properties);
+assert_eq($properties, ["private c=4", "private secret=1", "protected b=3", "public a=2", "public dynamic=6"]);
+assert_eq(integrate_objects_scoped_property($child, IntegrationTestObjectsParent::class, "secret"), 1);
+
+class IntegrationTestObjectsMagic {
+    private $hidden = 1;
+
+    public function __get($name) {
+        return "magic $name";
+    }
+
+    public function __isset($name) {
+        return false;
+    }
+
+    public function __unset($name) {}
+}
+
+// The `__get` is called because the property is inaccessible in the scope.
+$magic = new IntegrationTestObjectsMagic();
+assert_eq(integrate_objects_scoped_property($magic, IntegrationTestObjectsParent::class, "hidden"), "magic hidden");
+assert_eq(integrate_objects_scoped_property($magic, IntegrationTestObjectsMagic::class, "hidden"), 1);
+assert_eq(integrate_objects_scoped_has_unset($magic, IntegrationTestObjectsParent::class, "hidden"), [false, false]);
+assert_eq(integrate_objects_scoped_has_unset($magic, IntegrationTestObjectsMagic::class, "hidden"), [true, false]);
+
+$child = new IntegrationTestObjectsChild();
+assert_eq(integrate_objects_scoped_has_unset($child, IntegrationTestObjectsParent::class, "secret"), [true, false]);
+
+// Test instantiate with properties
+#[AllowDynamicProperties]
+class IntegrationTestObjectsHydrated extends IntegrationTestObjectsParent {
+    public $id;
+    protected $name = "default";
+    private $secret = "default";
+
+    public function __construct() {
+        throw new Exception("constructor shouldn't be called");
+    }
+
+    public function toArray() {
+        return [$this->id, $this->name, $this->secret];
+    }
+}
+
+$hydrated = integrate_objects_instantiate_with_properties(IntegrationTestObjectsHydrated::class, [
+    "id" => 1,
+    "name" => "foo",
+    "secret" => "bar",
+    "\0IntegrationTestObjectsParent\0secret" => 2,
+    "extra" => true,
+]);
+assert_eq($hydrated->toArray(), [1, "foo", "bar"]);
+assert_eq($hydrated->extra, true);
+assert_eq(integrate_objects_scoped_property($hydrated, IntegrationTestObjectsParent::class, "secret"), 2);
+
+abstract class IntegrationTestObjectsAbstract {}
+interface IntegrationTestObjectsInterface {}
+assert_throw(function () { integrate_objects_instantiate_with_properties(IntegrationTestObjectsAbstract::class, []); }, "Error", 0, "Cannot instantiate abstract class IntegrationTestObjectsAbstract");
+assert_throw(function () { integrate_objects_instantiate_with_properties(IntegrationTestObjectsInterface::class, []); }, "Error", 0, "Cannot instantiate interface IntegrationTestObjectsInterface");
+
+if (PHP_VERSION_ID >= 70400) {
+    eval('class IntegrationTestObjectsTyped { public int $id; }');
+
+    $typed = integrate_objects_instantiate_with_properties(IntegrationTestObjectsTyped::class, ["id" => 10]);
+    assert_eq($typed->id, 10);
+
+    try {
+        integrate_objects_instantiate_with_properties(IntegrationTestObjectsTyped::class, ["id" => "10"]);
+        throw new AssertionError("TypeError not throws");
+    } catch (TypeError $e) {
+    }
+}
+
+if (PHP_VERSION_ID >= 80100) {
+    eval('enum IntegrationTestObjectsEnum {}');
+    assert_throw(function () { integrate_objects_instantiate_with_properties(IntegrationTestObjectsEnum::class, []); }, "Error", 0, "Cannot instantiate enum IntegrationTestObjectsEnum");
+}
 
 $a = new IntegrationTest\Objects\A();
 assert_throw(function () use ($a) { $a2 = clone $a; }, "Error", 0, "Trying to clone an uncloneable object of class IntegrationTest\\Objects\\A");
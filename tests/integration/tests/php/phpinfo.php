@@ -10,8 +10,25 @@
 // NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
 // See the Mulan PSL v2 for more details.
 
+require_once __DIR__ . '/_common.php';
+
 ob_start();
 phpinfo();
 $output = ob_get_contents();
 ob_end_clean();
 echo substr($output, 0, 100) . '...';
+
+ob_start();
+(new ReflectionExtension("integration"))->info();
+$info = ob_get_clean();
+
+if (PHP_SAPI == "cli") {
+    assert_true(strpos($info, "Integration Pools") !== false);
+    assert_true(strpos($info, "Pool => Size => Idle\n") !== false);
+    assert_true(strpos($info, "default => 10 => 8\n") !== false);
+    assert_true(strpos($info, "info called => ") !== false);
+} else {
+    assert_true(strpos($info, '<th colspan="3">Integration Pools</th>') !== false);
+    assert_true(strpos($info, '<tr class="h"><th>Pool</th><th>Size</th><th>Idle</th></tr>') !== false);
+    assert_true(strpos($info, '<td class="e">backup </td><td class="v">5 </td><td class="v"><i>no value</i> </td>') !== false);
+}
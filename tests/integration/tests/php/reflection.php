@@ -16,3 +16,12 @@ require_once __DIR__ . '/_common.php';
 $ext = new ReflectionExtension("integration");
 assert_eq($ext->getName(), "integration");
 assert_eq($ext->getVersion(), "0.0.0");
+
+$dependencies = $ext->getDependencies();
+assert_eq($dependencies["standard"], "Required");
+assert_eq($dependencies["sockets"], "Optional");
+assert_eq($dependencies["integration_conflicts"], "Conflicts");
+
+// The required module is started up before this module.
+$extensions = array_map('strtolower', get_loaded_extensions());
+assert_true(array_search("standard", $extensions) < array_search("integration", $extensions));
@@ -0,0 +1,36 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+assert_eq(integrate_replace_greet("world"), "hello world!");
+assert_eq(str_rot13("hello"), "uryyb");
+
+$counter = new IntegrationTest\Replace\Counter();
+assert_eq($counter->count(), 103);
+
+class IntegrateReplaceCounter extends IntegrationTest\Replace\Counter {}
+
+$counter = new IntegrateReplaceCounter();
+assert_eq($counter->count(), 103);
+
+$calls = integrate_replace_array_object_count_calls();
+$arr = new ArrayObject([1, 2, 3]);
+assert_eq($arr->count(), 3);
+assert_eq(integrate_replace_array_object_count_calls(), $calls + 1);
+
+class IntegrateReplaceArrayObject extends ArrayObject {}
+
+$arr = new IntegrateReplaceArrayObject([1, 2]);
+assert_eq($arr->count(), 2);
+assert_eq(integrate_replace_array_object_count_calls(), $calls + 2);
@@ -0,0 +1,47 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+assert_eq(integrate_sapi_name(), PHP_SAPI);
+assert_eq(integrate_sapi_headers_sent(), headers_sent());
+
+$request = integrate_sapi_request();
+
+if (PHP_SAPI == 'cli') {
+    assert_eq($request['method'], null);
+    assert_eq($request['uri'], null);
+    assert_eq(integrate_sapi_request_body(), '');
+} else {
+    assert_eq($request['method'], 'POST');
+    assert_eq($request['uri'], '/sapi.php?foo=bar');
+    assert_eq($request['query_string'], 'foo=bar');
+    assert_eq($request['content_type'], 'application/json');
+    assert_eq($request['content_length'], strlen('{"hello":"world"}'));
+    assert_eq(integrate_sapi_request_body(), '{"hello":"world"}');
+    assert_false(integrate_sapi_no_headers());
+
+    integrate_sapi_add_header('X-Integrate-Foo: foo', true);
+    integrate_sapi_add_header('X-Integrate-Bar: bar', false);
+    integrate_sapi_add_header('X-Integrate-Bar: baz', false);
+    integrate_sapi_add_header('X-Integrate-Foo: foo2', true);
+    integrate_sapi_remove_header('X-Integrate-Bar');
+    $headers = headers_list();
+    assert_true(in_array('X-Integrate-Foo: foo2', $headers));
+    assert_false(in_array('X-Integrate-Foo: foo', $headers));
+    assert_false(in_array('X-Integrate-Bar: bar', $headers));
+}
+
+assert_eq(integrate_sapi_response_code(), http_response_code() ?: 0);
+integrate_sapi_set_response_code(201);
+assert_eq(integrate_sapi_response_code(), 201);
+assert_eq(http_response_code(), 201);
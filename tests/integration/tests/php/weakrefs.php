@@ -0,0 +1,66 @@
+<?php
+
+// Copyright (c) 2022 PHPER Framework Team
+// PHPER is licensed under Mulan PSL v2.
+// You can use this software according to the terms and conditions of the Mulan
+// PSL v2. You may obtain a copy of Mulan PSL v2 at:
+//          http://license.coscl.org.cn/MulanPSL2
+// THIS SOFTWARE IS PROVIDED ON AN "AS IS" BASIS, WITHOUT WARRANTIES OF ANY
+// KIND, EITHER EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO
+// NON-INFRINGEMENT, MERCHANTABILITY OR FIT FOR A PARTICULAR PURPOSE.
+// See the Mulan PSL v2 for more details.
+
+
+require_once __DIR__ . '/_common.php';
+
+// PHP 7.4+ is required to use weak references
+if (PHP_VERSION_ID < 70400) {
+    echo "PHP 7.4+ required for weakrefs tests\n";
+    exit(0);
+}
+
+// Test ZWeakRef::upgrade
+assert_true(integrate_weakrefs_upgrade(new stdClass()));
+
+// Test the weak references don't keep the listeners alive
+$dispatcher = new IntegrationTest\WeakRefs\Dispatcher();
+$a = new stdClass();
+$b = new stdClass();
+$dispatcher->addListener($a);
+$dispatcher->addListener($b);
+assert_eq($dispatcher->count(), 2);
+unset($a);
+assert_eq($dispatcher->count(), 1);
+unset($b);
+assert_eq($dispatcher->count(), 0);
+
+// PHP 8.1+ is required to use weak map
+if (PHP_VERSION_ID < 80100) {
+    exit(0);
+}
+
+// Test ZWeakMap
+$metadata = new IntegrationTest\WeakRefs\Metadata();
+$a = new stdClass();
+$b = new stdClass();
+assert_eq($metadata->set($a, "foo"), null);
+assert_eq($metadata->set($b, "bar"), null);
+assert_eq($metadata->set($a, "baz"), "foo");
+assert_eq($metadata->count(), 2);
+assert_true($metadata->has($a));
+assert_eq($metadata->get($a), "baz");
+assert_eq($metadata->get(new stdClass()), null);
+
+// The entry is removed when the object is destroyed
+unset($a);
+assert_eq($metadata->count(), 1);
+
+assert_eq($metadata->remove($b), "bar");
+assert_false($metadata->has($b));
+assert_eq($metadata->count(), 0);
+
+// The map drops the remaining entries when it is destroyed
+$c = new stdClass();
+$metadata->set($c, [1, 2, 3]);
+unset($metadata);
+unset($c);